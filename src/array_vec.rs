@@ -0,0 +1,256 @@
+//! A vector with a fixed capacity that lives on the stack.
+
+use crate::init::{Guard, PartialInit};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::Deref;
+
+/// A list of up to `CAP` elements stored inline, returned by operations whose output length
+/// is only known at runtime but bounded by the length of an array,
+/// such as [`run_length_encode`](crate::ArrayFoldExt::run_length_encode).
+///
+/// It dereferences to a slice of the stored elements.
+pub struct ArrayVec<T, const CAP: usize> {
+    array: MaybeUninit<[T; CAP]>,
+    len: usize,
+}
+
+impl<T, const CAP: usize> ArrayVec<T, CAP> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            array: MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    /// Takes the elements out of the vector into a writer, which drops them unless they are given back.
+    #[inline]
+    fn writer(&mut self) -> PartialInit<'_, T, CAP> {
+        let len = core::mem::take(&mut self.len);
+        // SAFETY: the first `len` elements are initialized, and the vector no longer claims them.
+        unsafe { PartialInit::resume(&mut self.array, len) }
+    }
+
+    /// Panics if the vector is full.
+    #[inline]
+    pub(crate) fn push(&mut self, value: T) {
+        let mut writer = self.writer();
+        writer.write_next(value);
+        self.len = writer.finish_prefix_in_place().len();
+    }
+
+    /// Removes the last element, if there is one.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        // SAFETY: the element at `len` was initialized, and is no longer counted after this.
+        Some(unsafe { self.array.as_ptr().cast::<T>().add(self.len).read() })
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of elements, `CAP`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Borrows the elements.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { core::slice::from_raw_parts(self.array.as_ptr().cast::<T>(), self.len) }
+    }
+
+    /// Returns the elements as an array if there are exactly `CAP` of them,
+    /// and gives the vector back otherwise.
+    #[inline]
+    pub fn into_array(self) -> Result<[T; CAP], Self> {
+        if self.len() == CAP {
+            Ok(ManuallyDrop::new(self).writer().finish())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T, const CAP: usize> Drop for ArrayVec<T, CAP> {
+    fn drop(&mut self) {
+        // Dropping the writer drops the elements.
+        drop(self.writer());
+    }
+}
+
+impl<T, const CAP: usize> Deref for ArrayVec<T, CAP> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> IntoIterator for ArrayVec<T, CAP> {
+    type Item = T;
+    type IntoIter = ArrayVecIntoIter<T, CAP>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let this = ManuallyDrop::new(self);
+        ArrayVecIntoIter {
+            // SAFETY: `this` is never used or dropped again, so the elements move to the iterator.
+            array: unsafe { core::ptr::read(&this.array) },
+            start: 0,
+            end: this.len,
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a ArrayVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for ArrayVec<T, CAP> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for elem in self {
+            clone.push(elem.clone());
+        }
+        clone
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for ArrayVec<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize, const CAP2: usize> PartialEq<ArrayVec<U, CAP2>>
+    for ArrayVec<T, CAP>
+{
+    fn eq(&self, other: &ArrayVec<U, CAP2>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<[U]> for ArrayVec<T, CAP> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for ArrayVec<T, CAP> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, const CAP: usize> Eq for ArrayVec<T, CAP> {}
+
+/// An iterator that moves the elements out of an [`ArrayVec`], in order.
+///
+/// The elements that haven't been yielded are dropped along with the iterator.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::ArrayExt;
+/// let (_, parsed) = ["1", "2", "x"].try_map_partial(|s| s.parse::<u8>()).unwrap_err();
+/// let mut parsed = parsed.into_iter();
+/// assert_eq!(parsed.next(), Some(1));
+/// assert_eq!(parsed.as_slice(), [2]);
+/// ```
+pub struct ArrayVecIntoIter<T, const CAP: usize> {
+    array: MaybeUninit<[T; CAP]>,
+    start: usize,
+    end: usize,
+}
+
+impl<T, const CAP: usize> ArrayVecIntoIter<T, CAP> {
+    /// Borrows the elements that haven't been yielded yet.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the elements from `start` to `end` are initialized.
+        unsafe {
+            core::slice::from_raw_parts(
+                self.array.as_ptr().cast::<T>().add(self.start),
+                self.end - self.start,
+            )
+        }
+    }
+}
+
+impl<T, const CAP: usize> Iterator for ArrayVecIntoIter<T, CAP> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        // SAFETY: the element at `start` is initialized, and is no longer counted after this.
+        let value = unsafe { self.array.as_ptr().cast::<T>().add(self.start).read() };
+        self.start += 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for ArrayVecIntoIter<T, CAP> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: the element at `end` was initialized, and is no longer counted after this.
+        Some(unsafe { self.array.as_ptr().cast::<T>().add(self.end).read() })
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for ArrayVecIntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for ArrayVecIntoIter<T, CAP> {}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for ArrayVecIntoIter<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayVecIntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const CAP: usize> Drop for ArrayVecIntoIter<T, CAP> {
+    fn drop(&mut self) {
+        // Dropping the guard drops the elements that haven't been yielded.
+        drop(Guard {
+            // SAFETY: `start` is at most `CAP`, so the pointer stays inside or one past the end of the array.
+            dst: unsafe { self.array.as_mut_ptr().cast::<T>().add(self.start) },
+            initialized: self.end - self.start,
+        });
+    }
+}
@@ -0,0 +1,104 @@
+//! Compile-time assertions on const generic lengths.
+//!
+//! Each assertion has an `OK` constant that fails to evaluate when the assertion doesn't hold.
+//! Evaluating it in a function body turns a violated requirement into a compile error
+//! for the instantiation that violates it:
+//!
+//! ```
+//! use array_try_map::assert::AssertLe;
+//!
+//! fn prefix<const M: usize, const N: usize>(array: &[u8; N]) -> [u8; M] {
+//!     let () = AssertLe::<M, N>::OK;
+//!     std::array::from_fn(|i| array[i])
+//! }
+//!
+//! assert_eq!(prefix::<2, 3>(&[1, 2, 3]), [1, 2]);
+//! ```
+//!
+//! ```compile_fail
+//! # use array_try_map::assert::AssertLe;
+//! # fn prefix<const M: usize, const N: usize>(array: &[u8; N]) -> [u8; M] {
+//! #     let () = AssertLe::<M, N>::OK;
+//! #     std::array::from_fn(|i| array[i])
+//! # }
+//! prefix::<4, 3>(&[1, 2, 3]);
+//! ```
+//!
+//! The error is only reported when the code is actually compiled, so `cargo check` may not show it.
+
+/// Asserts that `N` is not zero.
+pub struct AssertNonZero<const N: usize>;
+
+impl<const N: usize> AssertNonZero<N> {
+    /// Fails to evaluate if `N` is zero.
+    pub const OK: () = assert!(N != 0, "the length must not be zero");
+}
+
+/// Asserts that `A` is equal to `B`.
+pub struct AssertEq<const A: usize, const B: usize>;
+
+impl<const A: usize, const B: usize> AssertEq<A, B> {
+    /// Fails to evaluate if `A` isn't equal to `B`.
+    pub const OK: () = assert!(A == B, "the lengths must be equal");
+}
+
+/// Asserts that `A` is less than or equal to `B`.
+pub struct AssertLe<const A: usize, const B: usize>;
+
+impl<const A: usize, const B: usize> AssertLe<A, B> {
+    /// Fails to evaluate if `A` is greater than `B`.
+    pub const OK: () = assert!(
+        A <= B,
+        "the first length must not be greater than the second"
+    );
+}
+
+/// Asserts that `A` is less than `B`.
+pub struct AssertLt<const A: usize, const B: usize>;
+
+impl<const A: usize, const B: usize> AssertLt<A, B> {
+    /// Fails to evaluate if `A` is not less than `B`.
+    pub const OK: () = assert!(A < B, "the first length must be less than the second");
+}
+
+/// Asserts that `B` divides `A` evenly. `B` must not be zero.
+pub struct AssertDivisible<const A: usize, const B: usize>;
+
+impl<const A: usize, const B: usize> AssertDivisible<A, B> {
+    /// Fails to evaluate if `B` is zero or `A` isn't a multiple of `B`.
+    pub const OK: () = assert!(
+        B != 0 && A.is_multiple_of(B),
+        "the first length must be a multiple of the second"
+    );
+}
+
+/// Asserts that `A + B + C` is equal to `N`.
+pub(crate) struct AssertSum3<const A: usize, const B: usize, const C: usize, const N: usize>;
+
+impl<const A: usize, const B: usize, const C: usize, const N: usize> AssertSum3<A, B, C, N> {
+    pub(crate) const OK: () = assert!(
+        A + B + C == N,
+        "the lengths of the ranges must add up to the length of the array"
+    );
+}
+
+/// Asserts that an array of length `N` has exactly `M` windows of length `K`.
+pub(crate) struct AssertWindows<const N: usize, const K: usize, const M: usize>;
+
+impl<const N: usize, const K: usize, const M: usize> AssertWindows<N, K, M> {
+    pub(crate) const OK: () = assert!(
+        K != 0 && M + K == N + 1,
+        "the output length must be the number of windows of length `K` in the array"
+    );
+}
+
+/// Asserts that `T` and `U` have the same size and alignment.
+pub(crate) struct AssertSameLayout<T, U>(core::marker::PhantomData<(T, U)>);
+
+impl<T, U> AssertSameLayout<T, U> {
+    pub(crate) const OK: () = assert!(
+        core::mem::size_of::<T>() == core::mem::size_of::<U>()
+            && core::mem::align_of::<T>() == core::mem::align_of::<U>(),
+        "the element types must have the same size and alignment"
+    );
+}
@@ -0,0 +1,146 @@
+//! Mappings with asynchronous functions.
+
+use core::future::{poll_fn, Future};
+use core::mem::MaybeUninit;
+use core::pin::pin;
+use core::task::Poll;
+
+use crate::init::PartialInit;
+use crate::ArrayExt;
+
+/// Version of [`try_map`](crate::ArrayMapExt::try_map) for functions that return futures.
+/// The future for every element is awaited before the next element is passed to `f`.
+///
+/// The returned future is cancel-safe: dropping it before it completes drops the already
+/// initialized elements and the elements that haven't been passed to `f`.
+///
+/// # Errors
+///
+/// If a future returned by `f` resolves to an [`Err`], that error will be returned by this function.
+/// The already initialized elements will be dropped when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` or a future it returns panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_map_async;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// async fn fetch(id: u32) -> Result<String, u32> {
+///     if id < 100 { Ok(format!("item {}", id)) } else { Err(id) }
+/// }
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// let items = pin!(try_map_async([1, 2], fetch));
+/// assert_eq!(
+///     items.poll(&mut cx),
+///     Poll::Ready(Ok(["item 1".to_string(), "item 2".to_string()]))
+/// );
+///
+/// let items = pin!(try_map_async([1, 200, 3], fetch));
+/// assert_eq!(items.poll(&mut cx), Poll::Ready(Err(200)));
+/// ```
+pub async fn try_map_async<T, U, E, F, Fut, const N: usize>(
+    array: [T; N],
+    mut f: F,
+) -> Result<[U; N], E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let mut array_out = MaybeUninit::<[U; N]>::uninit();
+    // SAFETY: no element is initialized yet.
+    let mut dst = unsafe { PartialInit::resume(&mut array_out, 0) };
+    for src in IntoIterator::into_iter(array) {
+        dst.write_next(f(src).await?);
+    }
+    dst.finish_in_place();
+    // SAFETY: every element has been initialized, and `dst` no longer drops them.
+    Ok(unsafe { array_out.assume_init() })
+}
+
+/// Version of [`try_map_async`] that runs the futures for all elements concurrently.
+///
+/// Every element is passed to `f` up front, and the futures are then polled together
+/// until all of them are done or one of them fails. Nothing is allocated.
+///
+/// The returned future is cancel-safe: dropping it before it completes drops the pending futures
+/// and the outputs of the finished ones.
+///
+/// # Errors
+///
+/// If a future returned by `f` resolves to an [`Err`], that error will be returned by this function.
+/// The remaining futures and the outputs of the finished ones will be dropped when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` or a future it returns panics.
+/// The remaining futures and the outputs of the finished ones will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_join_map;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// async fn call(replica: &str) -> Result<usize, String> {
+///     if replica.is_empty() { Err("empty address".to_string()) } else { Ok(replica.len()) }
+/// }
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// let replies = pin!(try_join_map(["a", "bb", "ccc"], call));
+/// assert_eq!(replies.poll(&mut cx), Poll::Ready(Ok([1, 2, 3])));
+///
+/// let replies = pin!(try_join_map(["a", "", "ccc"], call));
+/// assert_eq!(replies.poll(&mut cx), Poll::Ready(Err("empty address".to_string())));
+/// ```
+pub async fn try_join_map<T, U, E, F, Fut, const N: usize>(
+    array: [T; N],
+    mut f: F,
+) -> Result<[U; N], E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let mut futures = pin!(array.map2(|src| Some(f(src))));
+    let mut outputs = [(); N].map2(|()| None);
+    poll_fn(|cx| {
+        let mut done = true;
+        for (index, output) in outputs.iter_mut().enumerate() {
+            // SAFETY: the futures are never moved out of the pinned array.
+            let mut slot = unsafe {
+                futures
+                    .as_mut()
+                    .map_unchecked_mut(|futures| &mut futures[index])
+            };
+            let Some(future) = slot.as_mut().as_pin_mut() else {
+                continue;
+            };
+            match future.poll(cx) {
+                Poll::Ready(Ok(value)) => {
+                    *output = Some(value);
+                    slot.set(None);
+                }
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => done = false,
+            }
+        }
+        if done {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await?;
+    Ok(outputs.map2(Option::unwrap))
+}
@@ -0,0 +1,122 @@
+//! Primitive casts used by [`map_as`](crate::ArrayConvertExt::map_as) and [`checked_cast`](crate::ArrayConvertExt::checked_cast).
+
+use core::convert::TryFrom;
+
+/// Conversion between primitive types with the semantics of an `as` cast.
+///
+/// This is implemented for every pair of primitive numeric types.
+/// Like `as`, the conversion may truncate, wrap or lose precision.
+pub trait AsPrimitive<U>: Copy {
+    /// Converts `self` to `U` as if by `self as U`.
+    fn as_(self) -> U;
+}
+
+macro_rules! impl_as_primitive {
+    ($($ty:ty),*) => {
+        impl_as_primitive!(@to [$($ty),*] $($ty),*);
+    };
+    (@to $from:tt $($to:ty),*) => {
+        $(
+            impl_as_primitive!(@from $from $to);
+        )*
+    };
+    (@from [$($from:ty),*] $to:ty) => {
+        $(
+            impl AsPrimitive<$to> for $from {
+                #[inline]
+                fn as_(self) -> $to {
+                    self as $to
+                }
+            }
+        )*
+    };
+}
+
+impl_as_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Conversion between primitive types that fails instead of losing range.
+///
+/// Unlike [`AsPrimitive`], this returns [`None`] when the value does not fit into `U`.
+/// Floats are truncated towards zero when converted to integers, like `as`,
+/// but NaN, infinities and values outside of the integer's range are rejected.
+/// Conversions to floats only fail if a finite value would become infinite.
+pub trait CheckedCast<U>: AsPrimitive<U> {
+    /// Converts `self` to `U`, returning [`None`] if it is out of range.
+    fn checked_cast(self) -> Option<U>;
+}
+
+macro_rules! impl_checked_cast {
+    ($kind:ident: [$($from:ty),*] => $to:tt) => {
+        $(
+            impl_checked_cast!(@$kind $from => $to);
+        )*
+    };
+    (@int_to_int $from:ty => [$($to:ty),*]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    <$to>::try_from(self).ok()
+                }
+            }
+        )*
+    };
+    (@int_to_float $from:ty => [$($to:ty),*]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    let value = self as $to;
+                    if value.is_infinite() {
+                        None
+                    } else {
+                        Some(value)
+                    }
+                }
+            }
+        )*
+    };
+    (@float_to_int $from:ty => [$($to:ty),*]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    // `MIN - 1` and `MAX + 1` are the exclusive bounds of the values that truncate into range.
+                    // If `MIN - 1` rounds to `MIN`, no float lies strictly between them.
+                    let min = <$to>::MIN as $from;
+                    let above_min = if min - 1.0 == min { self >= min } else { self > min - 1.0 };
+                    // `MAX / 2 + 1` is a power of two, so it and its double are exact.
+                    let max_plus_one = (<$to>::MAX / 2 + 1) as $from * 2.0;
+                    if above_min && self < max_plus_one {
+                        Some(self as $to)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+    (@float_to_float $from:ty => [$($to:ty),*]) => {
+        $(
+            impl CheckedCast<$to> for $from {
+                #[inline]
+                fn checked_cast(self) -> Option<$to> {
+                    let cast = self as $to;
+                    if self.is_finite() && cast.is_infinite() {
+                        None
+                    } else {
+                        Some(cast)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_cast!(int_to_int: [u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]
+    => [u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]);
+impl_checked_cast!(int_to_float: [u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]
+    => [f32, f64]);
+impl_checked_cast!(float_to_int: [f32, f64]
+    => [u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]);
+impl_checked_cast!(float_to_float: [f32, f64] => [f32, f64]);
@@ -0,0 +1,99 @@
+//! Constant-time operations on arrays of integers, for secret material such as keys and MACs.
+//!
+//! The functions never branch on or index by the contents of the arrays or the `choice` flags,
+//! and they use [`black_box`] to discourage the optimizer from adding such branches.
+//! Like with any Rust code, this is a best effort and not a guarantee from the compiler.
+//!
+//! ```
+//! use array_try_map::ct;
+//!
+//! let expected_tag = [0x3Fu8, 0x9A, 0x01, 0xC4];
+//! let received_tag = [0x3Fu8, 0x9A, 0x01, 0xC5];
+//! assert!(!ct::eq(&expected_tag, &received_tag));
+//!
+//! let mut key = [0u8; 4];
+//! ct::assign(&mut key, &[1, 2, 3, 4], true);
+//! assert_eq!(key, [1, 2, 3, 4]);
+//! ```
+
+use core::hint::black_box;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::init::from_fn;
+
+/// Integer types that the constant-time operations work on.
+pub trait Word:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    /// The value with no bits set.
+    const ZERO: Self;
+
+    /// Returns the value with all bits set if `choice` is `true`, and [`ZERO`](Word::ZERO) otherwise.
+    fn mask(choice: bool) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),+) => {$(
+        impl Word for $t {
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn mask(choice: bool) -> Self {
+                (0 as $t).wrapping_sub(choice as $t)
+            }
+        }
+    )+};
+}
+
+impl_word!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Returns `true` if the arrays are equal, taking the same time wherever they differ.
+#[inline]
+pub fn eq<T: Word, const N: usize>(a: &[T; N], b: &[T; N]) -> bool {
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(T::ZERO, |acc, (&a, &b)| black_box(acc | (a ^ b)));
+    diff == T::ZERO
+}
+
+/// Returns a copy of `a` if `choice` is `false` and of `b` if it is `true`.
+///
+/// # Examples
+///
+/// ```
+/// use array_try_map::ct;
+///
+/// assert_eq!(ct::select(&[1u32, 2], &[3, 4], false), [1, 2]);
+/// assert_eq!(ct::select(&[1u32, 2], &[3, 4], true), [3, 4]);
+/// ```
+#[inline]
+pub fn select<T: Word, const N: usize>(a: &[T; N], b: &[T; N], choice: bool) -> [T; N] {
+    let mask = T::mask(black_box(choice));
+    from_fn(|i| (a[i] & !mask) | (b[i] & mask))
+}
+
+/// Overwrites `dst` with `src` if `choice` is `true`, and leaves it unchanged otherwise.
+#[inline]
+pub fn assign<T: Word, const N: usize>(dst: &mut [T; N], src: &[T; N], choice: bool) {
+    let mask = T::mask(black_box(choice));
+    for (dst, &src) in dst.iter_mut().zip(src) {
+        *dst = (*dst & !mask) | (src & mask);
+    }
+}
+
+/// Swaps the contents of `a` and `b` if `choice` is `true`, and leaves them unchanged otherwise.
+#[inline]
+pub fn swap<T: Word, const N: usize>(a: &mut [T; N], b: &mut [T; N], choice: bool) {
+    let mask = T::mask(black_box(choice));
+    for (a, b) in a.iter_mut().zip(b) {
+        let diff = (*a ^ *b) & mask;
+        *a = *a ^ diff;
+        *b = *b ^ diff;
+    }
+}
@@ -0,0 +1,109 @@
+//! Forwarding of the extension traits to newtypes around arrays.
+
+/// Access to an array, either directly or inside a newtype.
+///
+/// The extension traits of [`ArrayExt`](crate::ArrayExt) and [`NonEmptyArrayExt`](crate::NonEmptyArrayExt)
+/// are implemented for every type that implements this trait, including arrays themselves.
+/// [`delegate_array_ext!`](crate::delegate_array_ext) implements it for a newtype.
+pub trait AsArray<T, const N: usize> {
+    /// Moves the array out.
+    fn into_array(self) -> [T; N];
+
+    /// Borrows the array.
+    fn as_array(&self) -> &[T; N];
+
+    /// Mutably borrows the array.
+    fn as_mut_array(&mut self) -> &mut [T; N];
+}
+
+impl<T, const N: usize> AsArray<T, N> for [T; N] {
+    #[inline(always)]
+    fn into_array(self) -> [T; N] {
+        self
+    }
+
+    #[inline(always)]
+    fn as_array(&self) -> &[T; N] {
+        self
+    }
+
+    #[inline(always)]
+    fn as_mut_array(&mut self) -> &mut [T; N] {
+        self
+    }
+}
+
+/// Makes the methods of every trait of [`ArrayExt`](crate::ArrayExt) and [`NonEmptyArrayExt`](crate::NonEmptyArrayExt)
+/// available on a newtype around an array, by implementing [`AsArray`] for it and [`From`] the array.
+///
+/// `delegate_array_ext!(Newtype => [T; N])` forwards to the field `0` of a tuple struct,
+/// and `delegate_array_ext!(Newtype.field => [T; N])` forwards to the named field.
+/// The newtype must not have other fields.
+/// The methods return plain arrays, which can be wrapped again with [`From`].
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{delegate_array_ext, ArrayExt, NonEmptyArrayExt};
+/// struct Rgb([u8; 3]);
+///
+/// delegate_array_ext!(Rgb => [u8; 3]);
+///
+/// let bright = Rgb([100, 150, 220]).try_map(|c| c.checked_add(50).ok_or("overflow"));
+/// assert_eq!(bright, Err("overflow"));
+///
+/// let color = Rgb([10, 20, 30]);
+/// assert_eq!(*color.last(), 30);
+/// let dark = Rgb::from(color.map2(|c| c / 2));
+/// assert_eq!(dark.map_into::<u32>(), [5, 10, 15]);
+///
+/// struct Samples {
+///     data: [f32; 4],
+/// }
+///
+/// delegate_array_ext!(Samples.data => [f32; 4]);
+///
+/// let samples = Samples { data: [0.5, 1.5, 2.5, 3.5] };
+/// assert_eq!(samples.map_as::<i32>(), [0, 1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! delegate_array_ext {
+    ($ty:ident => [$t:ty; $n:expr]) => {
+        $crate::delegate_array_ext!(@impl $ty, 0, $t, $n);
+
+        impl ::core::convert::From<[$t; $n]> for $ty {
+            #[inline]
+            fn from(array: [$t; $n]) -> Self {
+                Self(array)
+            }
+        }
+    };
+    ($ty:ident . $field:ident => [$t:ty; $n:expr]) => {
+        $crate::delegate_array_ext!(@impl $ty, $field, $t, $n);
+
+        impl ::core::convert::From<[$t; $n]> for $ty {
+            #[inline]
+            fn from(array: [$t; $n]) -> Self {
+                Self { $field: array }
+            }
+        }
+    };
+    (@impl $ty:ident, $field:tt, $t:ty, $n:expr) => {
+        impl $crate::AsArray<$t, { $n }> for $ty {
+            #[inline]
+            fn into_array(self) -> [$t; $n] {
+                self.$field
+            }
+
+            #[inline]
+            fn as_array(&self) -> &[$t; $n] {
+                &self.$field
+            }
+
+            #[inline]
+            fn as_mut_array(&mut self) -> &mut [$t; $n] {
+                &mut self.$field
+            }
+        }
+    };
+}
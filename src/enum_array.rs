@@ -0,0 +1,222 @@
+//! An array indexed by the variants of an enum.
+
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{ArrayExt, VariantArray};
+
+/// An array with one value per variant of the enum `K`, indexed by `K` instead of [`usize`].
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{impl_variant_array, EnumArray};
+/// #[derive(Clone, Copy)]
+/// enum State {
+///     Idle,
+///     Running,
+///     Done,
+/// }
+///
+/// impl_variant_array!(State { Idle, Running, Done });
+///
+/// let mut visits = EnumArray::<State, u32, 3>::from_fn(|_| 0);
+/// visits[State::Running] += 2;
+/// visits[State::Done] += 1;
+///
+/// let doubled = visits.map(|v| v * 2);
+/// assert_eq!(doubled.into_array(), [0, 4, 2]);
+/// ```
+pub struct EnumArray<K, V, const N: usize> {
+    array: [V; N],
+    keys: PhantomData<fn(K) -> K>,
+}
+
+impl<K, V, const N: usize> EnumArray<K, V, N>
+where
+    K: VariantArray<N>,
+{
+    /// Creates an enum array by calling `f` with every variant in order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(K) -> V,
+    {
+        Self::from_array(K::from_variants(f))
+    }
+
+    /// Creates an enum array by calling `f` with every variant in order until it returns an error.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    pub fn try_from_fn<E, F>(f: F) -> Result<Self, E>
+    where
+        F: FnMut(K) -> Result<V, E>,
+    {
+        K::try_from_variants(f).map(Self::from_array)
+    }
+}
+
+impl<K, V, const N: usize> EnumArray<K, V, N> {
+    /// Wraps an array whose elements are in the order of [`VariantArray::VARIANTS`].
+    pub const fn from_array(array: [V; N]) -> Self {
+        Self {
+            array,
+            keys: PhantomData,
+        }
+    }
+
+    /// Returns the underlying array.
+    pub fn into_array(self) -> [V; N] {
+        self.array
+    }
+
+    /// Returns a reference to the underlying array.
+    pub const fn as_array(&self) -> &[V; N] {
+        &self.array
+    }
+
+    /// Returns a mutable reference to the underlying array.
+    pub fn as_mut_array(&mut self) -> &mut [V; N] {
+        &mut self.array
+    }
+
+    /// Applies `f` to every value, keeping the keys.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    pub fn map<U, F>(self, f: F) -> EnumArray<K, U, N>
+    where
+        F: FnMut(V) -> U,
+    {
+        EnumArray::from_array(self.array.map2(f))
+    }
+
+    /// Applies `f` to every value until it returns an error, keeping the keys.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{impl_variant_array, EnumArray};
+    /// enum Axis {
+    ///     X,
+    ///     Y,
+    /// }
+    ///
+    /// impl_variant_array!(Axis { X, Y });
+    ///
+    /// let raw = EnumArray::<Axis, &str, 2>::from_array(["1.5", "-2"]);
+    /// let parsed = raw.try_map(str::parse::<f32>).unwrap();
+    /// assert_eq!(parsed[Axis::Y], -2.0);
+    /// ```
+    pub fn try_map<U, E, F>(self, f: F) -> Result<EnumArray<K, U, N>, E>
+    where
+        F: FnMut(V) -> Result<U, E>,
+    {
+        self.array.try_map(f).map(EnumArray::from_array)
+    }
+
+    /// Combines the values for each key in two enum arrays with `f`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements and the remaining values will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{impl_variant_array, EnumArray};
+    /// enum Axis {
+    ///     X,
+    ///     Y,
+    /// }
+    ///
+    /// impl_variant_array!(Axis { X, Y });
+    ///
+    /// let a = EnumArray::<Axis, i32, 2>::from_array([1, 2]);
+    /// let b = EnumArray::<Axis, i32, 2>::from_array([10, 20]);
+    /// assert_eq!(a.zip_map(b, |a, b| a + b)[Axis::Y], 22);
+    /// ```
+    pub fn zip_map<W, U, F>(self, other: EnumArray<K, W, N>, f: F) -> EnumArray<K, U, N>
+    where
+        F: FnMut(V, W) -> U,
+    {
+        EnumArray::from_array(self.array.zip_with(other.array, f))
+    }
+}
+
+impl<K, V, const N: usize> Index<K> for EnumArray<K, V, N>
+where
+    K: VariantArray<N>,
+{
+    type Output = V;
+
+    fn index(&self, key: K) -> &V {
+        &self.array[key.variant_index()]
+    }
+}
+
+impl<K, V, const N: usize> IndexMut<K> for EnumArray<K, V, N>
+where
+    K: VariantArray<N>,
+{
+    fn index_mut(&mut self, key: K) -> &mut V {
+        &mut self.array[key.variant_index()]
+    }
+}
+
+impl<K, V: Clone, const N: usize> Clone for EnumArray<K, V, N> {
+    fn clone(&self) -> Self {
+        Self::from_array(self.array.clone())
+    }
+}
+
+impl<K, V: Copy, const N: usize> Copy for EnumArray<K, V, N> {}
+
+impl<K, V: fmt::Debug, const N: usize> fmt::Debug for EnumArray<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.array.fmt(f)
+    }
+}
+
+impl<K, V: PartialEq, const N: usize> PartialEq for EnumArray<K, V, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.array == other.array
+    }
+}
+
+impl<K, V: Eq, const N: usize> Eq for EnumArray<K, V, N> {}
+
+impl<K, V: Hash, const N: usize> Hash for EnumArray<K, V, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.array.hash(state)
+    }
+}
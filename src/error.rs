@@ -0,0 +1,159 @@
+//! Error types returned by the methods of this crate.
+
+use core::fmt;
+
+/// An error returned by a mapping that reports which element failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryMapError<E> {
+    /// The index of the element that failed.
+    pub index: usize,
+    /// The error produced for that element.
+    pub error: E,
+}
+
+impl<E> TryMapError<E> {
+    /// Discards the index and returns the underlying error.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+/// Compares only the underlying error, ignoring the index.
+impl<E: PartialEq> PartialEq<E> for TryMapError<E> {
+    fn eq(&self, other: &E) -> bool {
+        self.error == *other
+    }
+}
+
+impl<E> AsRef<E> for TryMapError<E> {
+    fn as_ref(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TryMapError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "element {} failed: {}", self.index, self.error)
+    }
+}
+
+/// The error returned when a checked primitive cast is out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CastError;
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value out of range for the target type")
+    }
+}
+
+/// The error returned when a slice or collection doesn't have the length of the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LenError {
+    /// The length that was required.
+    pub expected: usize,
+    /// The length that was found.
+    pub actual: usize,
+}
+
+impl fmt::Display for LenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a length of {}, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+/// The error returned when an iterator doesn't yield exactly as many items as the array has elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollectArrayError {
+    /// The iterator ended early.
+    TooFew {
+        /// The number of items that were required.
+        expected: usize,
+        /// The number of items the iterator yielded.
+        actual: usize,
+    },
+    /// The iterator had items left over.
+    TooMany {
+        /// The number of items that were required.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for CollectArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectArrayError::TooFew { expected, actual } => write!(
+                f,
+                "expected {} items, but the iterator ended after {}",
+                expected, actual
+            ),
+            CollectArrayError::TooMany { expected } => {
+                write!(f, "expected {} items, but the iterator had more", expected)
+            }
+        }
+    }
+}
+
+/// The error returned when an iterator of [`Result`]s can't be collected into an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TryCollectError<E> {
+    /// The iterator yielded an error.
+    Element(E),
+    /// The iterator didn't yield exactly as many items as the array has elements.
+    Len(CollectArrayError),
+}
+
+impl<E: fmt::Display> fmt::Display for TryCollectError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryCollectError::Element(err) => err.fmt(f),
+            TryCollectError::Len(err) => err.fmt(f),
+        }
+    }
+}
+
+/// The error returned when a string can't be converted into an array of ASCII bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsciiError {
+    /// The string is ASCII but doesn't have the length of the array.
+    Len(LenError),
+    /// The string contains a non-ASCII character.
+    NonAscii {
+        /// The byte index of the first non-ASCII character.
+        index: usize,
+    },
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiError::Len(err) => err.fmt(f),
+            AsciiError::NonAscii { index } => write!(f, "non-ASCII character at index {}", index),
+        }
+    }
+}
+
+/// The error returned when an index is not less than the length it is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfBounds {
+    /// The position of the invalid index in the array of indices.
+    pub position: usize,
+    /// The invalid index.
+    pub index: usize,
+    /// The length the index had to be less than.
+    pub len: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} at position {} is out of bounds for length {}",
+            self.index, self.position, self.len
+        )
+    }
+}
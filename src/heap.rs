@@ -0,0 +1,228 @@
+//! Conversions between heap-allocated collections and arrays.
+
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use core::convert::TryInto;
+use core::mem::MaybeUninit;
+
+use crate::assert::AssertSameLayout;
+use crate::{in_place, ArrayExt, LenError, PartialInit};
+
+/// Maps a borrowed or owned slice into an array of length `N`.
+///
+/// The elements are passed to `f` by reference in both cases, so nothing is cloned.
+/// An owned slice is dropped once the mapping is done.
+///
+/// # Errors
+///
+/// If the slice doesn't have exactly `N` elements, a [`LenError`] with its actual length will be returned
+/// and `f` won't be called.
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_map_from_cow;
+/// use std::borrow::Cow;
+///
+/// let x: Cow<[u8]> = Cow::Borrowed(&[1, 2, 3]);
+/// let y = try_map_from_cow(x, |v| u32::from(*v) * 2);
+/// assert_eq!(y, Ok([2, 4, 6]));
+///
+/// let x: Cow<[u8]> = Cow::Owned(vec![1, 2]);
+/// let y = try_map_from_cow::<_, u32, _, 3>(x, |v| u32::from(*v));
+/// assert_eq!(y.unwrap_err().actual, 2);
+/// ```
+pub fn try_map_from_cow<T, U, F, const N: usize>(
+    cow: Cow<'_, [T]>,
+    f: F,
+) -> Result<[U; N], LenError>
+where
+    T: Clone,
+    F: FnMut(&T) -> U,
+{
+    let slice: &[T] = &cow;
+    let array: &[T; N] = slice.try_into().map_err(|_| LenError {
+        expected: N,
+        actual: slice.len(),
+    })?;
+    Ok(array.each_ref().map2(f))
+}
+
+/// Converts a [`Vec`] with exactly `N` elements into an array.
+///
+/// # Errors
+///
+/// If the vector doesn't have exactly `N` elements, it will be returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::vec_into_array;
+/// let x = vec![1, 2, 3];
+/// assert_eq!(vec_into_array(x), Ok([1, 2, 3]));
+///
+/// let x = vec![1, 2];
+/// assert_eq!(vec_into_array::<_, 3>(x), Err(vec![1, 2]));
+/// ```
+pub fn vec_into_array<T, const N: usize>(vec: Vec<T>) -> Result<[T; N], Vec<T>> {
+    vec.try_into()
+}
+
+/// Converts the first `N` elements of a [`Vec`] into an array and returns the rest in a new [`Vec`].
+///
+/// # Errors
+///
+/// If the vector has fewer than `N` elements, it will be returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::vec_split_into_array;
+/// let x = vec![1, 2, 3, 4];
+/// assert_eq!(vec_split_into_array(x), Ok(([1, 2], vec![3, 4])));
+///
+/// let x = vec![1, 2];
+/// assert_eq!(vec_split_into_array::<_, 3>(x), Err(vec![1, 2]));
+/// ```
+pub fn vec_split_into_array<T, const N: usize>(
+    mut vec: Vec<T>,
+) -> Result<([T; N], Vec<T>), Vec<T>> {
+    if vec.len() < N {
+        return Err(vec);
+    }
+    let rest = vec.split_off(N);
+    match vec.try_into() {
+        Ok(array) => Ok((array, rest)),
+        Err(_) => unreachable!("the vector was truncated to `N` elements"),
+    }
+}
+
+/// Version of [`try_map`](crate::ArrayMapExt::try_map) that writes the results directly into a heap allocation.
+///
+/// The result is never built on the stack, so this can be used when `[U; N]` is too large for it.
+///
+/// # Errors
+///
+/// If `f` returns an [`Err`], that error will be returned by this function.
+/// The already initialized elements will be dropped and the allocation freed when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_map_boxed;
+/// let samples = [1u16; 1024];
+/// let scaled = try_map_boxed(samples, |s| s.checked_mul(1000).ok_or("overflow"));
+/// assert!(scaled.unwrap().iter().all(|&s| s == 1000));
+///
+/// let scaled = try_map_boxed([1u16, 100], |s| s.checked_mul(1000).ok_or("overflow"));
+/// assert_eq!(scaled, Err("overflow"));
+/// ```
+pub fn try_map_boxed<T, U, E, F, const N: usize>(array: [T; N], mut f: F) -> Result<Box<[U; N]>, E>
+where
+    F: FnMut(T) -> Result<U, E>,
+{
+    // SAFETY: an array of `MaybeUninit` doesn't need to be initialized.
+    let mut slots: Box<[MaybeUninit<U>; N]> = unsafe { Box::new_uninit().assume_init() };
+    let mut init = PartialInit::new(&mut slots);
+    for src in IntoIterator::into_iter(array) {
+        init.write_next(f(src)?);
+    }
+    init.finish_in_place();
+    // SAFETY: all elements are initialized, and `[MaybeUninit<U>; N]` has the same layout as `[U; N]`.
+    Ok(unsafe { Box::from_raw(Box::into_raw(slots).cast::<[U; N]>()) })
+}
+
+/// Extension of `Box<[T; N]>` with mappings that reuse the allocation.
+pub trait BoxedArrayExt<T, const N: usize> {
+    /// Version of [`try_map_in_place`](crate::ArrayMapExt::try_map_in_place) that writes the results over the elements
+    /// inside the box, so no second allocation or copy of the array is made.
+    ///
+    /// `T` and `U` must have the same size and alignment, which is checked at compile time.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped
+    /// and the allocation freed when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::BoxedArrayExt;
+    /// let samples = Box::new([0.5f32; 4096]);
+    /// let pcm = samples.try_map_in_box(|s| {
+    ///     if (-1.0..=1.0).contains(&s) {
+    ///         Ok((s * i32::MAX as f32) as i32)
+    ///     } else {
+    ///         Err("clipped")
+    ///     }
+    /// });
+    /// assert!(pcm.unwrap().iter().all(|&s| s == 1 << 30));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::BoxedArrayExt;
+    /// let x = Box::new([1u8, 2]).try_map_in_box(|v| Ok::<_, ()>(u32::from(v)));
+    /// ```
+    fn try_map_in_box<F, U, E>(self, f: F) -> Result<Box<[U; N]>, E>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Infallible version of [`try_map_in_box`](BoxedArrayExt::try_map_in_box).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::BoxedArrayExt;
+    /// let pixels = Box::new([0x00FF_00FFu32; 1024]);
+    /// let inverted = pixels.map_in_box(|p| !p);
+    /// assert!(inverted.iter().all(|&p| p == 0xFF00_FF00));
+    /// ```
+    fn map_in_box<F, U>(self, f: F) -> Box<[U; N]>
+    where
+        F: FnMut(T) -> U;
+}
+
+impl<T, const N: usize> BoxedArrayExt<T, N> for Box<[T; N]> {
+    fn try_map_in_box<F, U, E>(self, f: F) -> Result<Box<[U; N]>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let () = AssertSameLayout::<T, U>::OK;
+        // SAFETY: `[MaybeUninit<T>; N]` has the same layout as `[T; N]`.
+        // Holding the elements as `MaybeUninit` frees the allocation without dropping them again
+        // if `f` fails or panics.
+        let mut slots = unsafe { Box::from_raw(Box::into_raw(self).cast::<[MaybeUninit<T>; N]>()) };
+        // SAFETY: the elements are initialized and `slots` won't drop them,
+        // and `T` and `U` have the same layout.
+        unsafe { in_place::try_map_slots(slots.as_mut_ptr().cast::<T>(), N, f)? };
+        // SAFETY: every element is now a `U`, and `[MaybeUninit<T>; N]` has the same layout as `[U; N]`.
+        Ok(unsafe { Box::from_raw(Box::into_raw(slots).cast::<[U; N]>()) })
+    }
+
+    fn map_in_box<F, U>(self, mut f: F) -> Box<[U; N]>
+    where
+        F: FnMut(T) -> U,
+    {
+        self.try_map_in_box::<_, _, !>(|src| Ok(f(src))).into_ok()
+    }
+}
@@ -0,0 +1,140 @@
+//! Indices that are statically known to be in bounds.
+
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{assert::AssertLt, init::from_fn};
+
+/// An index that is guaranteed to be less than `N`.
+///
+/// Indexing an array of length `N` with it skips the bounds check.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::Idx;
+/// let x = [10, 20, 30];
+/// let i = Idx::<3>::new(2).unwrap();
+/// assert_eq!(x[i], 30);
+///
+/// assert!(Idx::<3>::new(3).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Idx<const N: usize>(usize);
+
+impl<const N: usize> Idx<N> {
+    /// Returns the index if it is less than `N`.
+    #[inline]
+    pub const fn new(index: usize) -> Option<Self> {
+        if index < N {
+            Some(Self(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index `I`. Using an `I` that isn't less than `N` is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::Idx;
+    /// const LAST: Idx<4> = Idx::<4>::constant::<3>();
+    /// assert_eq!(LAST.get(), 3);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::Idx;
+    /// const OUT_OF_BOUNDS: Idx<4> = Idx::<4>::constant::<4>();
+    /// ```
+    #[inline]
+    pub const fn constant<const I: usize>() -> Self {
+        let () = AssertLt::<I, N>::OK;
+        Self(I)
+    }
+
+    /// Returns the index without checking that it is less than `N`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `N`.
+    #[inline]
+    pub const unsafe fn new_unchecked(index: usize) -> Self {
+        debug_assert!(index < N);
+        Self(index)
+    }
+
+    /// Returns the index as a [`usize`].
+    #[inline]
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Returns every index from `0` to `N - 1` in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::Idx;
+    /// let all = Idx::<3>::all();
+    /// assert_eq!(all.map(Idx::get), [0, 1, 2]);
+    /// ```
+    pub fn all() -> [Self; N] {
+        Self::map_indices(|i| i)
+    }
+
+    /// Creates an array by calling `f` with every index from `0` to `N - 1` in order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::Idx;
+    /// let x = [1, 2, 3];
+    /// let reversed = Idx::<3>::map_indices(|i| x[Idx::<3>::new(2 - i.get()).unwrap()]);
+    /// assert_eq!(reversed, [3, 2, 1]);
+    /// ```
+    pub fn map_indices<U, F>(mut f: F) -> [U; N]
+    where
+        F: FnMut(Self) -> U,
+    {
+        from_fn(|index| f(Self(index)))
+    }
+}
+
+impl<const N: usize> From<Idx<N>> for usize {
+    #[inline]
+    fn from(idx: Idx<N>) -> Self {
+        idx.0
+    }
+}
+
+impl<const N: usize> fmt::Display for Idx<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, const N: usize> Index<Idx<N>> for [T; N] {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: Idx<N>) -> &T {
+        // SAFETY: `idx` is less than `N`.
+        unsafe { self.get_unchecked(idx.0) }
+    }
+}
+
+impl<T, const N: usize> IndexMut<Idx<N>> for [T; N] {
+    #[inline]
+    fn index_mut(&mut self, idx: Idx<N>) -> &mut T {
+        // SAFETY: `idx` is less than `N`.
+        unsafe { self.get_unchecked_mut(idx.0) }
+    }
+}
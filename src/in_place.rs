@@ -0,0 +1,89 @@
+//! Mapping arrays without moving them.
+
+use crate::assert::AssertSameLayout;
+use crate::init::Guard;
+
+/// Maps an array that is never moved again, such as a DMA buffer or a static, without moving it.
+///
+/// Every element is read, passed to `f`, and the result is written back into the same slot,
+/// so the returned array has the same address as the original one.
+/// `T` and `U` must have the same size and alignment, which is checked at compile time.
+///
+/// The array is taken by `&'static mut` because the memory holds `U`s afterwards,
+/// so it must never be used as an array of `T`s again.
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already converted elements and the elements not yet passed to `f` will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::map_in_place_transmute;
+/// let buffer: &'static mut [u32; 4] = Box::leak(Box::new([1, 2, 3, 4]));
+/// let address = buffer.as_ptr() as usize;
+///
+/// let floats = map_in_place_transmute(buffer, |x| x as f32 / 2.0);
+/// assert_eq!(*floats, [0.5, 1.0, 1.5, 2.0]);
+/// assert_eq!(floats.as_ptr() as usize, address);
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::map_in_place_transmute;
+/// let buffer: &'static mut [u32; 4] = Box::leak(Box::new([1, 2, 3, 4]));
+/// map_in_place_transmute(buffer, |x| x as u64);
+/// ```
+pub fn map_in_place_transmute<T, U, F, const N: usize>(
+    array: &'static mut [T; N],
+    mut f: F,
+) -> &'static mut [U; N]
+where
+    F: FnMut(T) -> U,
+{
+    let () = AssertSameLayout::<T, U>::OK;
+    let ptr = array.as_mut_ptr();
+    // SAFETY: `ptr` points to `N` initialized `T`s that are never used as `T`s again,
+    // and `T` and `U` have the same layout.
+    unsafe { try_map_slots::<T, U, !, _>(ptr, N, |src| Ok(f(src))) }.into_ok();
+    // SAFETY: every slot now holds a `U`, and the original reference was consumed.
+    unsafe { &mut *ptr.cast::<[U; N]>() }
+}
+
+/// Replaces the `len` `T`s at `ptr` with the results of passing them to `f`, one slot at a time.
+///
+/// If `f` returns an error or panics, the already converted `U`s and the remaining `T`s are dropped.
+///
+/// # Safety
+///
+/// `ptr` must point to `len` initialized `T`s that are not used as `T`s afterwards,
+/// and `T` and `U` must have the same size and alignment.
+pub(crate) unsafe fn try_map_slots<T, U, E, F>(ptr: *mut T, len: usize, mut f: F) -> Result<(), E>
+where
+    F: FnMut(T) -> Result<U, E>,
+{
+    // The converted `U`s at the start and the `T`s that are still waiting at the end
+    // are dropped if `f` returns an error or panics.
+    let mut converted = Guard::<U> {
+        dst: ptr.cast::<U>(),
+        initialized: 0,
+    };
+    let mut remaining = Guard::<T> {
+        dst: ptr,
+        initialized: len,
+    };
+    while converted.initialized < len {
+        // SAFETY: the slot holds the first remaining `T`, which is moved out and replaced with a `U`,
+        // which fits because both types have the same layout.
+        unsafe {
+            let slot = remaining.dst;
+            remaining.dst = slot.add(1);
+            remaining.initialized -= 1;
+            let value = f(slot.read())?;
+            slot.cast::<U>().write(value);
+        }
+        converted.initialized += 1;
+    }
+    core::mem::forget(converted);
+    Ok(())
+}
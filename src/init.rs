@@ -0,0 +1,373 @@
+//! Primitives for initializing arrays one element at a time.
+
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+/// Initializes a borrowed array of [`MaybeUninit`] one element at a time,
+/// dropping the initialized elements if it is dropped before being finished.
+///
+/// This is the guard that fallible array construction needs so that an early return
+/// or a panic in the middle doesn't leak the elements written so far.
+/// It does the unsafe bookkeeping, so code using it doesn't need any `unsafe`.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::PartialInit;
+/// use std::mem::MaybeUninit;
+///
+/// fn parse_all<const N: usize>(input: [&str; N]) -> Result<[String; N], String> {
+///     let mut buf = [const { MaybeUninit::uninit() }; N];
+///     let mut init = PartialInit::new(&mut buf);
+///     for s in input {
+///         if s.is_empty() {
+///             // The strings written so far are dropped here.
+///             return Err(format!("empty string after {} elements", init.initialized()));
+///         }
+///         init.write_next(s.to_uppercase());
+///     }
+///     Ok(init.finish())
+/// }
+///
+/// assert_eq!(parse_all(["a", "b"]), Ok(["A".to_string(), "B".to_string()]));
+/// assert_eq!(parse_all(["a", "", "c"]), Err("empty string after 1 elements".to_string()));
+/// ```
+pub struct PartialInit<'a, T, const N: usize> {
+    guard: Guard<T>,
+    _buffer: PhantomData<&'a mut [MaybeUninit<T>; N]>,
+}
+
+impl<'a, T, const N: usize> PartialInit<'a, T, N> {
+    /// Starts initializing `array` from the beginning.
+    /// Anything already in it is ignored and will not be dropped.
+    #[inline]
+    pub fn new(array: &'a mut [MaybeUninit<T>; N]) -> Self {
+        Self {
+            guard: Guard {
+                dst: array.as_mut_ptr().cast::<T>(),
+                initialized: 0,
+            },
+            _buffer: PhantomData,
+        }
+    }
+
+    /// Continues initializing an array whose first `initialized` elements are already initialized.
+    ///
+    /// # Safety
+    ///
+    /// The first `initialized` elements of `array` must be initialized, and `initialized` must not be greater than `N`.
+    /// They are dropped along with the rest if the returned writer is dropped.
+    #[inline]
+    pub(crate) unsafe fn resume(array: &'a mut MaybeUninit<[T; N]>, initialized: usize) -> Self {
+        debug_assert!(initialized <= N);
+        Self {
+            guard: Guard {
+                dst: array.as_mut_ptr().cast::<T>(),
+                initialized,
+            },
+            _buffer: PhantomData,
+        }
+    }
+
+    /// Initializes the next element and returns a reference to it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if all elements are already initialized.
+    #[inline]
+    pub fn write_next(&mut self, value: T) -> &mut T {
+        assert!(
+            self.guard.initialized < N,
+            "all elements are already initialized"
+        );
+        // SAFETY: the slot is inside the buffer, and isn't initialized yet.
+        let elem = unsafe {
+            let slot = self.guard.dst.add(self.guard.initialized);
+            slot.write(value);
+            &mut *slot
+        };
+        self.guard.initialized += 1;
+        elem
+    }
+
+    /// Returns the number of initialized elements.
+    #[inline]
+    pub fn initialized(&self) -> usize {
+        self.guard.initialized
+    }
+
+    /// Returns `true` if all elements are initialized.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.guard.initialized == N
+    }
+
+    /// Borrows the initialized elements.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `initialized` elements are initialized.
+        unsafe { core::slice::from_raw_parts(self.guard.dst, self.guard.initialized) }
+    }
+
+    /// Mutably borrows the initialized elements.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `initialized` elements are initialized.
+        unsafe { core::slice::from_raw_parts_mut(self.guard.dst, self.guard.initialized) }
+    }
+
+    /// Moves the initialized array out of the buffer, which is left uninitialized.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not all elements are initialized.
+    /// The initialized elements will be dropped when a panic occurs.
+    #[inline]
+    pub fn finish(self) -> [T; N] {
+        let array = self.finish_in_place();
+        // SAFETY: the elements are no longer dropped by anyone else, and the buffer is not used as initialized again.
+        unsafe { core::ptr::read(array) }
+    }
+
+    /// Leaves the initialized array in the buffer and returns a reference to it.
+    ///
+    /// The elements will no longer be dropped automatically,
+    /// so the owner of the buffer becomes responsible for them.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not all elements are initialized.
+    /// The initialized elements will be dropped when a panic occurs.
+    #[inline]
+    pub fn finish_in_place(self) -> &'a mut [T; N] {
+        assert!(self.is_full(), "array is not fully initialized");
+        // SAFETY: all elements are initialized, and `[MaybeUninit<T>; N]` has the same layout as `[T; N]`.
+        unsafe { &mut *self.finish_prefix_in_place().as_mut_ptr().cast::<[T; N]>() }
+    }
+
+    /// Leaves the initialized elements at the start of the buffer and returns them,
+    /// even if not all elements are initialized.
+    ///
+    /// The elements will no longer be dropped automatically,
+    /// so the owner of the buffer becomes responsible for them.
+    #[inline]
+    pub fn finish_prefix_in_place(self) -> &'a mut [T] {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the first `initialized` elements are initialized,
+        // and the buffer stays borrowed for `'a` while `this` is never dropped.
+        unsafe { core::slice::from_raw_parts_mut(this.guard.dst, this.guard.initialized) }
+    }
+}
+
+/// Drops the initialized prefix of a buffer under construction if the construction is abandoned.
+///
+/// This is the only place that tracks partially initialized memory.
+/// [`PartialInit`] and the other builders of this crate are built on it.
+pub(crate) struct Guard<T> {
+    pub(crate) dst: *mut T,
+    pub(crate) initialized: usize,
+}
+
+// SAFETY: the guard owns the elements it drops, like a `Vec<T>` would.
+unsafe impl<T: Send> Send for Guard<T> {}
+// SAFETY: the guard only gives out shared references through `&self`.
+unsafe impl<T: Sync> Sync for Guard<T> {}
+
+impl<T> Drop for Guard<T> {
+    fn drop(&mut self) {
+        let initialized_part = core::ptr::slice_from_raw_parts_mut(self.dst, self.initialized);
+        // SAFETY: this raw slice will contain only initialized objects
+        // that's why, it is allowed to drop it.
+        unsafe {
+            core::ptr::drop_in_place(initialized_part);
+        }
+    }
+}
+
+/// Builds an array by calling `f` with the index of every element, in order,
+/// until the array is complete or an error is returned.
+///
+/// This is the primitive that [`try_map`](crate::ArrayMapExt::try_map) and the other fallible constructors
+/// of this crate are built on.
+///
+/// # Errors
+///
+/// If `f` returns an [`Err`], that error will be returned by this function.
+/// The already initialized elements will be dropped when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_init_array;
+/// use std::convert::TryFrom;
+///
+/// let squares: Result<[u8; 4], _> = try_init_array(|i| u8::try_from(i * i));
+/// assert_eq!(squares, Ok([0, 1, 4, 9]));
+///
+/// let squares: Result<[u8; 20], _> = try_init_array(|i| u8::try_from(i * i));
+/// assert!(squares.is_err());
+/// ```
+pub fn try_init_array<T, E, F, const N: usize>(mut f: F) -> Result<[T; N], E>
+where
+    F: FnMut(usize) -> Result<T, E>,
+{
+    //CHANGED FROM CORE: the whole array is one `MaybeUninit` that is written through the guard's pointer
+    // and converted with `assume_init`, so no second copy of it is made
+    let mut dst = MaybeUninit::<[T; N]>::uninit();
+    // Elements that don't need to be dropped don't need to be counted either,
+    // so the guard and its bookkeeping are skipped for them.
+    if !core::mem::needs_drop::<T>() {
+        let dst_ptr = dst.as_mut_ptr().cast::<T>();
+        for index in 0..N {
+            // SAFETY: `index` is less than `N`, so the slot is inside the array.
+            unsafe { dst_ptr.add(index).write(f(index)?) };
+        }
+        // SAFETY: every element has been initialized.
+        return Ok(unsafe { dst.assume_init() });
+    }
+    let mut guard = Guard {
+        dst: dst.as_mut_ptr().cast::<T>(),
+        initialized: 0,
+    };
+    while guard.initialized < N {
+        let index = guard.initialized;
+        //CHANGED FROM CORE: `?` on `f(index)` instead of directly inputting it into `dst.write`
+        // SAFETY: `index` is less than `N`, so the slot is inside the array.
+        unsafe { guard.dst.add(index).write(f(index)?) };
+        guard.initialized += 1;
+    }
+    core::mem::forget(guard);
+    // SAFETY: At this point we've properly initialized the whole array.
+    Ok(unsafe { dst.assume_init() }) //CHANGED FROM CORE: Ok-wrapped
+}
+
+/// Infallible version of [`try_init_array`] with its own loop, so no [`Result`] has to be passed around.
+pub(crate) fn init_array<T, F, const N: usize>(mut f: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    let mut dst = MaybeUninit::<[T; N]>::uninit();
+    if !core::mem::needs_drop::<T>() {
+        let dst_ptr = dst.as_mut_ptr().cast::<T>();
+        for index in 0..N {
+            // SAFETY: `index` is less than `N`, so the slot is inside the array.
+            unsafe { dst_ptr.add(index).write(f(index)) };
+        }
+        // SAFETY: every element has been initialized.
+        return unsafe { dst.assume_init() };
+    }
+    let mut guard = Guard {
+        dst: dst.as_mut_ptr().cast::<T>(),
+        initialized: 0,
+    };
+    while guard.initialized < N {
+        let index = guard.initialized;
+        // SAFETY: `index` is less than `N`, so the slot is inside the array.
+        unsafe { guard.dst.add(index).write(f(index)) };
+        guard.initialized += 1;
+    }
+    core::mem::forget(guard);
+    // SAFETY: every element has been initialized.
+    unsafe { dst.assume_init() }
+}
+
+/// Fallible counterpart of [`core::array::from_fn`].
+///
+/// This is the same as [`try_init_array`], under the name the standard library would give it.
+///
+/// # Errors
+///
+/// If `f` returns an [`Err`], that error will be returned by this function.
+/// The already initialized elements will be dropped when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::try_from_fn;
+/// let lines = ["10", "20", "30"];
+/// let parsed: Result<[u32; 3], _> = try_from_fn(|i| lines[i].parse());
+/// assert_eq!(parsed, Ok([10, 20, 30]));
+/// ```
+#[inline]
+pub fn try_from_fn<T, E, F, const N: usize>(f: F) -> Result<[T; N], E>
+where
+    F: FnMut(usize) -> Result<T, E>,
+{
+    try_init_array(f)
+}
+
+/// Builds an array by calling `f` with the index of every element, in order.
+///
+/// This behaves like [`core::array::from_fn`], and is the infallible version of [`try_from_fn`].
+///
+/// # Panics
+///
+/// This function panics if `f` panics.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::from_fn;
+/// let table: [u8; 5] = from_fn(|i| (i * i) as u8);
+/// assert_eq!(table, [0, 1, 4, 9, 16]);
+/// ```
+#[inline]
+pub fn from_fn<T, F, const N: usize>(f: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    init_array(f)
+}
+
+/// Builds an array by letting `f` write its elements in order through a [`PartialInit`].
+///
+/// This gives sequential write access for constructions that don't fit a per-index closure,
+/// such as ones that produce several elements at a time.
+///
+/// # Errors
+///
+/// If `f` returns an [`Err`], that error will be returned by this function.
+/// The already initialized elements will be dropped when an error occurs.
+///
+/// # Panics
+///
+/// This function panics if `f` panics, or if it returns [`Ok`] without initializing every element.
+/// The already initialized elements will be dropped when a panic occurs.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::init_with_writer;
+/// let pairs = [(1, 2), (3, 4)];
+/// let flat: Result<[i32; 4], ()> = init_with_writer(|w| {
+///     for (a, b) in pairs {
+///         w.write_next(a);
+///         w.write_next(b);
+///     }
+///     Ok(())
+/// });
+/// assert_eq!(flat, Ok([1, 2, 3, 4]));
+/// ```
+pub fn init_with_writer<T, E, F, const N: usize>(f: F) -> Result<[T; N], E>
+where
+    F: FnOnce(&mut PartialInit<'_, T, N>) -> Result<(), E>,
+{
+    let mut array = MaybeUninit::<[T; N]>::uninit();
+    // SAFETY: no element is initialized yet.
+    let mut writer = unsafe { PartialInit::resume(&mut array, 0) };
+    f(&mut writer)?;
+    writer.finish_in_place();
+    // SAFETY: every element has been initialized, and the writer no longer drops them.
+    Ok(unsafe { array.assume_init() })
+}
@@ -0,0 +1,108 @@
+//! Collection of iterators into arrays.
+
+use crate::{init, CollectArrayError, TryCollectError};
+
+/// Extension methods for iterators.
+pub trait IteratorExt: Iterator {
+    /// Collects exactly `N` items of the iterator into an array.
+    ///
+    /// # Errors
+    ///
+    /// If the iterator yields fewer or more than `N` items, a [`CollectArrayError`] will be returned.
+    /// The already collected items will be dropped when an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{CollectArrayError, IteratorExt};
+    /// let words = "a b c".split(' ').collect_array::<3>();
+    /// assert_eq!(words, Ok(["a", "b", "c"]));
+    ///
+    /// let words = "a b".split(' ').collect_array::<3>();
+    /// assert_eq!(words, Err(CollectArrayError::TooFew { expected: 3, actual: 2 }));
+    ///
+    /// let words = "a b c d".split(' ').collect_array::<3>();
+    /// assert_eq!(words, Err(CollectArrayError::TooMany { expected: 3 }));
+    /// ```
+    fn collect_array<const N: usize>(mut self) -> Result<[Self::Item; N], CollectArrayError>
+    where
+        Self: Sized,
+    {
+        let array = init::try_init_array(|index| {
+            self.next().ok_or(CollectArrayError::TooFew {
+                expected: N,
+                actual: index,
+            })
+        })?;
+        match self.next() {
+            Some(_) => Err(CollectArrayError::TooMany { expected: N }),
+            None => Ok(array),
+        }
+    }
+
+    /// Collects exactly `N` items of an iterator of [`Result`]s into an array,
+    /// stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// If the iterator yields an [`Err`] among its first `N` items,
+    /// that error will be returned by this function as [`TryCollectError::Element`].
+    /// If it yields fewer or more than `N` items, a [`TryCollectError::Len`] will be returned.
+    /// The already collected items will be dropped when an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{CollectArrayError, IteratorExt, TryCollectError};
+    /// let parsed = "1 2 3".split(' ').map(str::parse::<u8>).try_collect_array::<_, _, 3>();
+    /// assert_eq!(parsed, Ok([1, 2, 3]));
+    ///
+    /// let parsed = "1 x 3".split(' ').map(str::parse::<u8>).try_collect_array::<_, _, 3>();
+    /// assert!(matches!(parsed, Err(TryCollectError::Element(_))));
+    ///
+    /// let parsed = "1 2".split(' ').map(str::parse::<u8>).try_collect_array::<_, _, 3>();
+    /// assert_eq!(
+    ///     parsed,
+    ///     Err(TryCollectError::Len(CollectArrayError::TooFew { expected: 3, actual: 2 }))
+    /// );
+    /// ```
+    fn try_collect_array<T, E, const N: usize>(mut self) -> Result<[T; N], TryCollectError<E>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        let array = init::try_init_array(|index| match self.next() {
+            Some(item) => item.map_err(TryCollectError::Element),
+            None => Err(TryCollectError::Len(CollectArrayError::TooFew {
+                expected: N,
+                actual: index,
+            })),
+        })?;
+        match self.next() {
+            Some(_) => Err(TryCollectError::Len(CollectArrayError::TooMany {
+                expected: N,
+            })),
+            None => Ok(array),
+        }
+    }
+
+    /// Takes the next `N` items of the iterator as an array, leaving the rest of it untouched.
+    ///
+    /// If the iterator ends before `N` items, [`None`] is returned
+    /// and the items that were taken are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::IteratorExt;
+    /// let mut bytes = b"GIF89a\x01\x00".iter().copied();
+    /// assert_eq!(bytes.next_array(), Some(*b"GIF"));
+    /// assert_eq!(bytes.next_array(), Some(*b"89a"));
+    /// assert_eq!(bytes.next_array::<3>(), None);
+    /// assert_eq!(bytes.next(), None);
+    /// ```
+    fn next_array<const N: usize>(&mut self) -> Option<[Self::Item; N]> {
+        init::try_init_array(|_| self.next().ok_or(())).ok()
+    }
+}
+
+impl<I: Iterator + ?Sized> IteratorExt for I {}
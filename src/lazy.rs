@@ -0,0 +1,118 @@
+//! Arrays whose elements are computed on first access.
+
+use core::{cell::OnceCell, fmt};
+
+use crate::ArrayExt;
+
+/// An array of length `N` whose elements are computed by `F` the first time they are accessed.
+///
+/// Each element is computed at most once and then cached.
+/// This is useful for expensive per-index tables of which only a few entries are needed.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::LazyArray;
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let squares = LazyArray::<u64, _, 1000>::new(|i| {
+///     calls.set(calls.get() + 1);
+///     (i as u64) * (i as u64)
+/// });
+///
+/// assert_eq!(*squares.get(12), 144);
+/// assert_eq!(*squares.get(12), 144);
+/// assert_eq!(calls.get(), 1);
+/// ```
+pub struct LazyArray<T, F, const N: usize> {
+    cells: [OnceCell<T>; N],
+    f: F,
+}
+
+impl<T, F, const N: usize> LazyArray<T, F, N> {
+    /// Creates a lazy array where the element at index `i` is computed by `f(i)`.
+    pub fn new(f: F) -> Self {
+        Self {
+            cells: [const { OnceCell::new() }; N],
+            f,
+        }
+    }
+
+    /// Returns the element at `index` if it has already been computed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is not less than `N`.
+    pub fn get_if_computed(&self, index: usize) -> Option<&T> {
+        self.cells[index].get()
+    }
+
+    /// Returns the element at `index`, computing it if it hasn't been yet.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is not less than `N`, or if the generator panics.
+    /// A panicking generator leaves the element uncomputed.
+    pub fn get(&self, index: usize) -> &T
+    where
+        F: Fn(usize) -> T,
+    {
+        self.cells[index].get_or_init(|| (self.f)(index))
+    }
+
+    /// Returns the element at `index`, computing it with a fallible generator if it hasn't been yet.
+    ///
+    /// # Errors
+    ///
+    /// If the generator returns an [`Err`], that error will be returned by this function
+    /// and the element will stay uncomputed, so the next access tries again.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is not less than `N`, or if the generator panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::LazyArray;
+    /// let lines = ["1", "two", "3"];
+    /// let parsed = LazyArray::<u32, _, 3>::new(|i: usize| lines[i].parse::<u32>());
+    ///
+    /// assert_eq!(parsed.try_get(0), Ok(&1));
+    /// assert!(parsed.try_get(1).is_err());
+    /// assert!(parsed.get_if_computed(1).is_none());
+    /// ```
+    pub fn try_get<E>(&self, index: usize) -> Result<&T, E>
+    where
+        F: Fn(usize) -> Result<T, E>,
+    {
+        let cell = &self.cells[index];
+        if let Some(value) = cell.get() {
+            return Ok(value);
+        }
+        let value = (self.f)(index)?;
+        Ok(cell.get_or_init(|| value))
+    }
+
+    /// Computes every remaining element and returns the array.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the generator panics.
+    /// All computed elements will be dropped when a panic occurs.
+    pub fn into_array(self) -> [T; N]
+    where
+        F: Fn(usize) -> T,
+    {
+        let f = self.f;
+        self.cells
+            .map_indexed(|index, cell| cell.into_inner().unwrap_or_else(|| f(index)))
+    }
+}
+
+impl<T: fmt::Debug, F, const N: usize> fmt::Debug for LazyArray<T, F, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.cells.iter()).finish()
+    }
+}
@@ -1,23 +1,153 @@
-//! Adds [`try_map`](ArrayExt::try_map) and [`map2`](ArrayExt::try_map) methods to arrays.
+//! Adds [`try_map`](ArrayMapExt::try_map) and [`map2`](ArrayMapExt::map2) methods to arrays.
 //!
 //! This crate requires nightly.
+//!
+//! The extension traits of this crate can be imported at once from the [`prelude`],
+//! except for the ones whose method names collide with slice methods.
+//!
+//! Conversions between arrays and tuples whose fields all have the same type aren't provided here,
+//! because core already implements [`From`] in both directions for up to 12 elements:
+//!
+//! ```
+//! # use array_try_map::ArrayExt;
+//! let (r, g, b): (u8, u8, u8) = [255, 128, 0].into();
+//! assert_eq!((r, g, b), (255, 128, 0));
+//!
+//! let x: [i32; 3] = (1, 2, 3).into();
+//! assert_eq!(x.map2(|v| v * 2), [2, 4, 6]);
+//! ```
+//!
+//! # Features
+//!
+//! - `alloc`: conversions between heap-allocated collections and arrays, such as `vec_into_array` and `try_map_boxed`.
+//! - `const-exprs`: APIs that compute lengths in their types, such as `TypedBuilder` and `ReshapeArrayExt`.
+//!   This enables the incomplete `generic_const_exprs` feature.
+//! - `trace`: hooks in the `trace` module that observe `try_map` and `map2` while they process elements.
 
 #![doc(html_root_url = "https://docs.rs/array_try_map/0.1.0")]
-
 #![no_std]
 #![feature(
-    min_const_generics,
-    maybe_uninit_uninit_array,
-    maybe_uninit_extra,
-    maybe_uninit_slice,
-    array_value_iter,
     never_type,
+    trait_alias,
+    try_trait_v2,
+    try_trait_v2_residual,
     unwrap_infallible
 )]
+#![cfg_attr(feature = "const-exprs", feature(generic_const_exprs))]
+#![cfg_attr(feature = "const-exprs", allow(incomplete_features))]
 #![deny(missing_docs)]
+#![allow(unstable_name_collisions)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-/// Extension of `[T; N]` to add methods
-pub trait ArrayExt<T, const N: usize> {
+mod array_vec;
+pub mod assert;
+mod async_map;
+mod cast;
+pub mod ct;
+mod delegate;
+mod enum_array;
+mod error;
+#[cfg(feature = "alloc")]
+mod heap;
+mod idx;
+mod in_place;
+mod init;
+mod iter;
+mod lazy;
+mod macros;
+mod matrix;
+mod nested;
+mod newtype;
+mod non_empty;
+pub mod prelude;
+mod random;
+#[cfg(feature = "const-exprs")]
+mod reshape;
+mod ring;
+mod rle;
+mod slice;
+mod soa;
+mod testing;
+mod text;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "const-exprs")]
+mod typed_builder;
+mod unrolled;
+mod variants;
+
+pub use array_vec::{ArrayVec, ArrayVecIntoIter};
+pub use async_map::{try_join_map, try_map_async};
+pub use cast::{AsPrimitive, CheckedCast};
+pub use delegate::AsArray;
+pub use enum_array::EnumArray;
+pub use error::{
+    AsciiError, CastError, CollectArrayError, LenError, OutOfBounds, TryCollectError, TryMapError,
+};
+#[cfg(feature = "alloc")]
+pub use heap::{
+    try_map_boxed, try_map_from_cow, vec_into_array, vec_split_into_array, BoxedArrayExt,
+};
+pub use idx::Idx;
+pub use in_place::map_in_place_transmute;
+pub use init::{from_fn, init_with_writer, try_from_fn, try_init_array, PartialInit};
+pub use iter::IteratorExt;
+pub use lazy::LazyArray;
+pub use matrix::MatrixExt;
+pub use nested::{flatten_options, flatten_results, OptionArrayExt, ResultArrayExt};
+pub use newtype::TransparentWrapper;
+pub use non_empty::NonEmptyArrayExt;
+pub use random::RandomIndex;
+#[cfg(feature = "const-exprs")]
+pub use reshape::{FlattenArrayExt, ReshapeArrayExt};
+pub use ring::RingArray;
+pub use rle::run_length_decode;
+pub use slice::{as_array, as_mut_array, to_array};
+pub use soa::{StructOfArrays, UnzipArrayExt};
+pub use text::{str_to_ascii, str_to_chars};
+#[cfg(feature = "const-exprs")]
+pub use typed_builder::TypedBuilder;
+pub use variants::VariantArray;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::{assert_same_len, split_prefix};
+    pub use crate::testing::{differences, FULL_DIFF_MAX_LEN};
+}
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{ControlFlow, FromResidual, Residual, Try};
+
+/// All extension methods of `[T; N]` that work for any length, as one trait alias.
+///
+/// Importing `ArrayExt` brings the methods of every trait it is made of into scope,
+/// and an `ArrayExt<T, N>` bound requires all of them.
+///
+/// # Examples
+///
+/// ```
+/// use array_try_map::ArrayExt;
+///
+/// fn doubled<A: ArrayExt<u8, 3>>(x: A) -> Option<[u8; 3]> {
+///     x.try_map(|v| v.checked_mul(2).ok_or(())).ok()
+/// }
+///
+/// assert_eq!(doubled([1, 2, 3]), Some([2, 4, 6]));
+/// assert_eq!([1, 2, 3].reversed().zip([4, 5, 6]), [(3, 4), (2, 5), (1, 6)]);
+/// ```
+pub trait ArrayExt<T, const N: usize> = ArrayMapExt<T, N>
+    + ArrayZipExt<T, N>
+    + ArrayWindowExt<T, N>
+    + ArrayConvertExt<T, N>
+    + ArrayFoldExt<T, N>
+    + ArrayReorderExt<T, N>;
+
+/// Extension of `[T; N]` with fallible, indexed and stateful versions of `map`.
+pub trait ArrayMapExt<T, const N: usize> {
     /// Fallible version of `map`.
     /// The provided function will be run on every element until the array ends or an error is returned.
     ///
@@ -48,7 +178,8 @@ pub trait ArrayExt<T, const N: usize> {
     where
         F: FnMut(T) -> Result<U, E>;
 
-    /// Example of how `map` could be reimplemented in terms of [`try_map`](ArrayExt::try_map).
+    /// Infallible version of [`try_map`](ArrayMapExt::try_map), like `map` but with its own loop
+    /// that drops the already mapped elements and the remaining ones if `f` panics.
     ///
     /// # Panics
     ///
@@ -75,64 +206,1984 @@ pub trait ArrayExt<T, const N: usize> {
     fn map2<F, U>(self, f: F) -> [U; N]
     where
         F: FnMut(T) -> U;
-}
 
-impl<T, const N: usize> ArrayExt<T, N> for [T; N] {
-    // code here is modified code from core
-    fn try_map<F, U, E>(self, mut f: F) -> Result<[U; N], E>
+    /// Version of [`try_map`](ArrayMapExt::try_map) for functions that return an [`Option`].
+    /// The provided function will be run on every element until the array ends or [`None`] is returned.
+    ///
+    /// If `f` returns [`None`] for any element, [`None`] will be returned by this function.
+    /// The already initialized elements will be dropped when that happens.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// assert_eq!(['1', '2', '3'].opt_map(|c| c.to_digit(10)), Some([1, 2, 3]));
+    /// assert_eq!(['1', 'x', '3'].opt_map(|c| c.to_digit(10)), None);
+    /// ```
+    fn opt_map<F, U>(self, f: F) -> Option<[U; N]>
+    where
+        F: FnMut(T) -> Option<U>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that works with any type implementing [`Try`],
+    /// such as [`Result`], [`Option`] and [`ControlFlow`].
+    /// The array is wrapped in the same kind of type that `f` returns.
+    ///
+    /// If `f` returns a value that short-circuits, such as an [`Err`] or [`None`],
+    /// the equivalent value will be returned by this function.
+    /// The already initialized elements will be dropped when that happens.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// use std::ops::ControlFlow;
+    ///
+    /// assert_eq!([1u8, 2].try_map_generic(|x| x.checked_mul(100)), Some([100, 200]));
+    /// assert_eq!([1u8, 3].try_map_generic(|x| x.checked_mul(100)), None);
+    ///
+    /// let res: Result<[u8; 2], _> = ["1", "2"].try_map_generic(str::parse);
+    /// assert_eq!(res, Ok([1, 2]));
+    ///
+    /// let flow = [1, 2, 3].try_map_generic(|x| {
+    ///     if x == 2 { ControlFlow::Break("found 2") } else { ControlFlow::Continue(x) }
+    /// });
+    /// assert_eq!(flow, ControlFlow::Break("found 2"));
+    /// ```
+    fn try_map_generic<F, R>(self, f: F) -> <R::Residual as Residual<[R::Output; N]>>::TryType
+    where
+        F: FnMut(T) -> R,
+        R: Try,
+        R::Residual: Residual<[R::Output; N]>;
+
+    /// Maps the elements until `f` asks to stop by returning [`ControlFlow::Break`],
+    /// for early exits that aren't errors, such as finding what was searched for.
+    ///
+    /// If `f` breaks, the value it breaks with will be returned by this function.
+    /// The already initialized elements will be dropped when that happens.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let commands = ["left", "left", "halt", "right"];
+    /// let moves = commands.map_break(|c| match c {
+    ///     "left" => ControlFlow::Continue(-1),
+    ///     "right" => ControlFlow::Continue(1),
+    ///     other => ControlFlow::Break(other),
+    /// });
+    /// assert_eq!(moves, ControlFlow::Break("halt"));
+    /// ```
+    fn map_break<F, B, U>(self, f: F) -> ControlFlow<B, [U; N]>
+    where
+        F: FnMut(T) -> ControlFlow<B, U>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that borrows the array instead of consuming it.
+    /// The provided function will be run on a reference to every element until the array ends or an error is returned.
+    ///
+    /// This is equivalent to `self.each_ref().try_map(f)`, using the `each_ref` method of arrays.
+    /// To project mutable references, call `try_map` on the result of `each_mut` instead.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let names = [String::from("3"), String::from("14")];
+    /// let numbers = names.try_map_ref(|s| s.parse::<u32>());
+    /// assert_eq!(numbers, Ok([3, 14]));
+    /// assert_eq!(names[1], "14");
+    ///
+    /// let mut counters = [0u8, 255];
+    /// let res = counters.each_mut().try_map(|c| {
+    ///     *c = c.checked_add(1).ok_or("overflow")?;
+    ///     Ok::<_, &str>(*c)
+    /// });
+    /// assert_eq!(res, Err("overflow"));
+    /// assert_eq!(counters, [1, 255]);
+    /// ```
+    fn try_map_ref<'a, F, U, E>(&'a self, f: F) -> Result<[U; N], E>
+    where
+        T: 'a,
+        F: FnMut(&'a T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that borrows the array and passes a clone of every element to `f`.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` or [`Clone::clone`] panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let paths = [String::from("a.txt"), String::from("b")];
+    /// let res = paths.try_map_cloned(|mut p| {
+    ///     if !p.ends_with(".txt") {
+    ///         return Err(p);
+    ///     }
+    ///     p.truncate(p.len() - 4);
+    ///     Ok(p)
+    /// });
+    /// assert_eq!(res, Err(String::from("b")));
+    /// assert_eq!(paths[0], "a.txt");
+    /// ```
+    fn try_map_cloned<F, U, E>(&self, f: F) -> Result<[U; N], E>
+    where
+        T: Clone,
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that borrows the array and passes a copy of every element to `f`.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// use std::convert::TryFrom;
+    ///
+    /// let readings = [12i16, -3, 40];
+    /// let res = readings.try_map_copied(|r| u8::try_from(r));
+    /// assert!(res.is_err());
+    /// assert_eq!(readings.try_map_copied(|r| Ok::<_, ()>(r * 2)), Ok([24, -6, 80]));
+    /// ```
+    fn try_map_copied<F, U, E>(&self, f: F) -> Result<[U; N], E>
+    where
+        T: Copy,
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that writes the results over the elements they were made from,
+    /// instead of into a second array.
+    /// Only one array is live while the elements are mapped, but the result is still returned by value.
+    ///
+    /// `T` and `U` must have the same size and alignment, which is checked at compile time.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let samples = [0.5f64; 4096];
+    /// let db = samples.try_map_in_place(|x| if x > 0.0 { Ok(20.0 * x.log10()) } else { Err("silence") });
+    /// assert!(db.unwrap().iter().all(|&x| x < 0.0));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ArrayExt;
+    /// let wide = [1u8, 2].try_map_in_place(|x| Ok::<u64, ()>(x.into()));
+    /// ```
+    fn try_map_in_place<F, U, E>(self, f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`map2`](ArrayMapExt::map2) that writes the results over the elements they were made from,
+    /// instead of into a second array.
+    /// Only one array is live while the elements are mapped, but the result is still returned by value.
+    ///
+    /// `T` and `U` must have the same size and alignment, which is checked at compile time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the elements not yet passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let bits = [1.0f32, -2.5].map_in_place(f32::to_bits);
+    /// assert_eq!(bits, [0x3F80_0000, 0xC020_0000]);
+    /// ```
+    fn map_in_place<F, U>(self, f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U;
+
+    /// Version of [`map2`](ArrayMapExt::map2) that also passes the index of each element to `f`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let labels = ["x", "y", "z"].map_indexed(|i, name| format!("{}{}", name, i));
+    /// assert_eq!(labels, ["x0", "y1", "z2"]);
+    /// ```
+    fn map_indexed<F, U>(self, f: F) -> [U; N]
+    where
+        F: FnMut(usize, T) -> U;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that also passes the index of each element to `f`.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let ports = ["8080", "443", "http"];
+    /// let res = ports.try_map_indexed(|i, port| {
+    ///     port.parse::<u16>().map_err(|_| format!("port {} is not a number: {:?}", i, port))
+    /// });
+    /// assert_eq!(res, Err("port 2 is not a number: \"http\"".to_string()));
+    /// ```
+    fn try_map_indexed<F, U, E>(self, f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(usize, T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that reports the index of the failing element
+    /// along with the error.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], a [`TryMapError`] with that error and the index of the element
+    /// will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let err = ["1", "2", "three"].try_map_enumerated(|s| s.parse::<u8>()).unwrap_err();
+    /// assert_eq!(err.index, 2);
+    /// assert_eq!(err.to_string(), "element 2 failed: invalid digit found in string");
+    /// assert_eq!(err, "x".parse::<u8>().unwrap_err());
+    /// ```
+    fn try_map_enumerated<F, U, E>(self, f: F) -> Result<[U; N], TryMapError<E>>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) that gives back the successfully mapped elements on errors,
+    /// instead of dropping them.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function
+    /// along with the elements mapped before it, in order.
+    /// The elements not yet passed to `f` will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let fields = ["12", "7", "x", "3"];
+    /// let (err, parsed) = fields.try_map_partial(|s| s.parse::<u8>()).unwrap_err();
+    /// assert_eq!(parsed, [12, 7]);
+    /// assert_eq!(err.to_string(), "invalid digit found in string");
+    ///
+    /// // The mapped elements can be moved out, even if they can't be cloned.
+    /// let total: u32 = parsed.into_iter().map(u32::from).sum();
+    /// assert_eq!(total, 19);
+    /// ```
+    fn try_map_partial<F, U, E>(self, f: F) -> Result<[U; N], (E, ArrayVec<U, N>)>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Maps the elements in order, passing the previous output to `f` along with each element.
+    /// The first element is paired with `init`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// // A simple low-pass filter.
+    /// let samples = [8.0, 0.0, 0.0, 8.0];
+    /// let smoothed = samples.map_with_prev(0.0, |prev, x| prev + (x - prev) / 2.0);
+    /// assert_eq!(smoothed, [4.0, 2.0, 1.0, 4.5]);
+    /// ```
+    fn map_with_prev<F, U>(self, init: U, f: F) -> [U; N]
+    where
+        F: FnMut(&U, T) -> U;
+
+    /// Fallible version of [`map_with_prev`](ArrayMapExt::map_with_prev).
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let deltas = [5u8, 10, 20];
+    /// let totals = deltas.try_map_with_prev(0u8, |prev, d| prev.checked_add(d).ok_or("overflow"));
+    /// assert_eq!(totals, Ok([5, 15, 35]));
+    ///
+    /// let deltas = [100u8, 100, 100];
+    /// let totals = deltas.try_map_with_prev(0u8, |prev, d| prev.checked_add(d).ok_or("overflow"));
+    /// assert_eq!(totals, Err("overflow"));
+    /// ```
+    fn try_map_with_prev<F, U, E>(self, init: U, f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(&U, T) -> Result<U, E>;
+
+    /// Maps the elements in order while threading a state through `f`, and returns the final state.
+    /// `f` receives the current state and an element and returns the next state and the output for the element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let deltas = [3, -1, 4];
+    /// let (positions, end) = deltas.map_accum(10, |pos, d| (pos + d, pos + d));
+    /// assert_eq!(positions, [13, 12, 16]);
+    /// assert_eq!(end, 16);
+    ///
+    /// let (labels, count) = ["a", "b"].map_accum(0, |n, s| (n + 1, format!("{}{}", s, n)));
+    /// assert_eq!(labels, ["a0", "b1"]);
+    /// assert_eq!(count, 2);
+    /// ```
+    fn map_accum<S, F, U>(self, state: S, f: F) -> ([U; N], S)
+    where
+        F: FnMut(S, T) -> (S, U);
+
+    /// Like [`map2`](ArrayMapExt::map2), but passes a mutable context to `f` along with each element.
+    ///
+    /// This avoids capturing a mutable resource, such as an arena, a random number generator or a writer,
+    /// in the closure while it is still needed elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut names = Vec::new();
+    /// let ids = ["a", "b", "a"].map_with(&mut names, |names, name| {
+    ///     names.iter().position(|n| n == &name).unwrap_or_else(|| {
+    ///         names.push(name);
+    ///         names.len() - 1
+    ///     })
+    /// });
+    /// assert_eq!(ids, [0, 1, 0]);
+    /// assert_eq!(names, ["a", "b"]);
+    /// ```
+    fn map_with<C, F, U>(self, ctx: &mut C, f: F) -> [U; N]
+    where
+        C: ?Sized,
+        F: FnMut(&mut C, T) -> U;
+
+    /// Like [`try_map`](ArrayMapExt::try_map), but passes a mutable context to `f` along with each element.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// use std::fmt::Write;
+    ///
+    /// let mut out = String::new();
+    /// let lens = [1, 22, 333].try_map_with(&mut out, |out, n| {
+    ///     let start = out.len();
+    ///     write!(out, "{} ", n)?;
+    ///     Ok::<_, std::fmt::Error>(out.len() - start)
+    /// });
+    /// assert_eq!(lens, Ok([2, 3, 4]));
+    /// assert_eq!(out, "1 22 333 ");
+    /// ```
+    fn try_map_with<C, F, U, E>(self, ctx: &mut C, f: F) -> Result<[U; N], E>
+    where
+        C: ?Sized,
+        F: FnMut(&mut C, T) -> Result<U, E>;
+
+    /// Like [`try_map`](ArrayMapExt::try_map), but calls `on_progress` with the index of the element
+    /// and the length of the array after each element is mapped successfully.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function,
+    /// and `on_progress` is not called for the failing element.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` or `on_progress` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut log = Vec::new();
+    /// let lens = ["a", "bb", "ccc"].try_map_with_progress(
+    ///     |s| Ok::<_, ()>(s.len()),
+    ///     |index, total| log.push(format!("{}/{}", index + 1, total)),
+    /// );
+    /// assert_eq!(lens, Ok([1, 2, 3]));
+    /// assert_eq!(log, ["1/3", "2/3", "3/3"]);
+    /// ```
+    fn try_map_with_progress<F, P, U, E>(self, f: F, on_progress: P) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+        P: FnMut(usize, usize);
+
+    /// Like [`try_map`](ArrayMapExt::try_map), but runs `f` on every element even after an error,
+    /// and records the errors with the indices of the failing elements in `errors`.
+    ///
+    /// Only as many errors as fit in `errors` are recorded, and the others are dropped.
+    /// The recorded errors are written to the start of the buffer and are never dropped by this function,
+    /// so the caller is responsible for reading or dropping them.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`] for any element, the number of recorded errors will be returned.
+    /// The successfully mapped elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs,
+    /// but the errors recorded so far will be leaked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut errors = [const { MaybeUninit::uninit() }; 2];
+    /// let res = ["1", "x", "3", "y", "z"].try_map_errors_into(|s| s.parse::<u8>(), &mut errors);
+    /// assert_eq!(res, Err(2));
+    ///
+    /// // SAFETY: the first 2 errors were recorded.
+    /// let (first, second) = unsafe { (errors[0].assume_init_read(), errors[1].assume_init_read()) };
+    /// assert_eq!((first.0, second.0), (1, 3));
+    /// ```
+    fn try_map_errors_into<F, U, E>(
+        self,
+        f: F,
+        errors: &mut [MaybeUninit<(usize, E)>],
+    ) -> Result<[U; N], usize>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`try_map`](ArrayMapExt::try_map) without the guard that cleans up after a panic,
+    /// for hot loops where the bookkeeping is measurable.
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics, the elements mapped before the panic are leaked instead of dropped,
+    /// while the elements not yet passed to `f` are still dropped as usual.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let y = [1u8, 2, 3].try_map_unchecked(|v| v.checked_add(1).ok_or("overflow"));
+    /// assert_eq!(y, Ok([2, 3, 4]));
+    /// ```
+    fn try_map_unchecked<F, U, E>(self, f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>;
+
+    /// Version of [`map2`](ArrayMapExt::map2) without the guard that cleans up after a panic,
+    /// for hot loops where the bookkeeping is measurable.
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics, the elements mapped before the panic are leaked instead of dropped,
+    /// while the elements not yet passed to `f` are still dropped as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let y = [1u32, 2, 3].map_unchecked(|v| v.wrapping_mul(3));
+    /// assert_eq!(y, [3, 6, 9]);
+    /// ```
+    fn map_unchecked<F, U>(self, f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U;
+}
+
+impl<Arr, T, const N: usize> ArrayMapExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    // code here is modified code from core
+    fn try_map<F, U, E>(self, mut f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        // Elements that don't need to be dropped can be read straight out of the array by index,
+        // which keeps the loop simple enough to be vectorized.
+        if !core::mem::needs_drop::<T>() {
+            let src = ManuallyDrop::new(array);
+            // SAFETY: every element is read exactly once, and the unread ones don't need to be dropped.
+            return init::try_init_array(|index| {
+                traced(index, || f(unsafe { core::ptr::read(&src[index]) }))
+            });
+        }
+        let mut iter = IntoIterator::into_iter(array);
+        init::try_init_array(|index| traced(index, || f(iter.next().unwrap())))
+    }
+
+    fn map2<F, U>(self, mut f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U,
+    {
+        let array = AsArray::into_array(self);
+        if !core::mem::needs_drop::<T>() {
+            let src = ManuallyDrop::new(array);
+            // SAFETY: every element is read exactly once, and the unread ones don't need to be dropped.
+            return init::init_array(|index| {
+                traced_infallible(index, || f(unsafe { core::ptr::read(&src[index]) }))
+            });
+        }
+        let mut iter = IntoIterator::into_iter(array);
+        init::init_array(|index| traced_infallible(index, || f(iter.next().unwrap())))
+    }
+
+    fn opt_map<F, U>(self, mut f: F) -> Option<[U; N]>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        self.try_map(|src| f(src).ok_or(())).ok()
+    }
+
+    fn try_map_generic<F, R>(self, mut f: F) -> <R::Residual as Residual<[R::Output; N]>>::TryType
+    where
+        F: FnMut(T) -> R,
+        R: Try,
+        R::Residual: Residual<[R::Output; N]>,
+    {
+        let array = AsArray::into_array(self);
+        let res = array.try_map(|src| match f(src).branch() {
+            ControlFlow::Continue(output) => Ok(output),
+            ControlFlow::Break(residual) => Err(residual),
+        });
+        match res {
+            Ok(array) => Try::from_output(array),
+            Err(residual) => FromResidual::from_residual(residual),
+        }
+    }
+
+    fn map_break<F, B, U>(self, f: F) -> ControlFlow<B, [U; N]>
+    where
+        F: FnMut(T) -> ControlFlow<B, U>,
+    {
+        self.try_map_generic(f)
+    }
+
+    fn try_map_ref<'a, F, U, E>(&'a self, mut f: F) -> Result<[U; N], E>
+    where
+        T: 'a,
+        F: FnMut(&'a T) -> Result<U, E>,
+    {
+        let array = AsArray::as_array(self);
+        init::try_init_array(|index| traced(index, || f(&array[index])))
+    }
+
+    fn try_map_cloned<F, U, E>(&self, mut f: F) -> Result<[U; N], E>
+    where
+        T: Clone,
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::as_array(self);
+        init::try_init_array(|index| traced(index, || f(array[index].clone())))
+    }
+
+    fn try_map_copied<F, U, E>(&self, mut f: F) -> Result<[U; N], E>
+    where
+        T: Copy,
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::as_array(self);
+        init::try_init_array(|index| traced(index, || f(array[index])))
+    }
+
+    fn try_map_in_place<F, U, E>(self, f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let () = assert::AssertSameLayout::<T, U>::OK;
+        let mut array = MaybeUninit::new(array);
+        let ptr = array.as_mut_ptr().cast::<T>();
+        // SAFETY: the elements are initialized and `array` won't drop them,
+        // and `T` and `U` have the same layout.
+        unsafe { in_place::try_map_slots(ptr, N, f)? };
+        // SAFETY: every element is now a `U`, and `[T; N]` and `[U; N]` have the same layout.
+        Ok(unsafe { ptr.cast::<[U; N]>().read() })
+    }
+
+    fn map_in_place<F, U>(self, mut f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U,
+    {
+        self.try_map_in_place::<_, _, !>(|src| Ok(f(src))).into_ok()
+    }
+
+    fn map_indexed<F, U>(self, mut f: F) -> [U; N]
+    where
+        F: FnMut(usize, T) -> U,
+    {
+        self.try_map_indexed::<_, _, !>(|index, src| Ok(f(index, src)))
+            .into_ok()
+    }
+
+    fn try_map_indexed<F, U, E>(self, mut f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(usize, T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let mut iter = IntoIterator::into_iter(array);
+        init::try_init_array(|index| traced(index, || f(index, iter.next().unwrap())))
+    }
+
+    fn try_map_enumerated<F, U, E>(self, mut f: F) -> Result<[U; N], TryMapError<E>>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        self.try_map_indexed(|index, src| f(src).map_err(|error| TryMapError { index, error }))
+    }
+
+    fn try_map_partial<F, U, E>(self, mut f: F) -> Result<[U; N], (E, ArrayVec<U, N>)>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let mut dst = ArrayVec::new();
+        for src in IntoIterator::into_iter(array) {
+            match f(src) {
+                Ok(value) => dst.push(value),
+                Err(error) => return Err((error, dst)),
+            }
+        }
+        Ok(dst.into_array().ok().unwrap())
+    }
+
+    fn map_with_prev<F, U>(self, init: U, mut f: F) -> [U; N]
+    where
+        F: FnMut(&U, T) -> U,
+    {
+        self.try_map_with_prev::<_, _, !>(init, |prev, src| Ok(f(prev, src)))
+            .into_ok()
+    }
+
+    fn try_map_with_prev<F, U, E>(self, init: U, mut f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(&U, T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        init::init_with_writer(|dst| {
+            for src in IntoIterator::into_iter(array) {
+                let next = f(dst.as_slice().last().unwrap_or(&init), src)?;
+                dst.write_next(next);
+            }
+            Ok(())
+        })
+    }
+
+    fn map_accum<S, F, U>(self, state: S, mut f: F) -> ([U; N], S)
+    where
+        F: FnMut(S, T) -> (S, U),
+    {
+        let array = AsArray::into_array(self);
+        let mut src = IntoIterator::into_iter(array);
+        // `state` is only empty while `f` runs.
+        let mut state = Some(state);
+        let dst = init::init_array(|_| {
+            let (next, value) = f(state.take().unwrap(), src.next().unwrap());
+            state = Some(next);
+            value
+        });
+        (dst, state.unwrap())
+    }
+
+    fn map_with<C, F, U>(self, ctx: &mut C, mut f: F) -> [U; N]
+    where
+        C: ?Sized,
+        F: FnMut(&mut C, T) -> U,
+    {
+        self.map2(|src| f(ctx, src))
+    }
+
+    fn try_map_with<C, F, U, E>(self, ctx: &mut C, mut f: F) -> Result<[U; N], E>
+    where
+        C: ?Sized,
+        F: FnMut(&mut C, T) -> Result<U, E>,
+    {
+        self.try_map(|src| f(ctx, src))
+    }
+
+    fn try_map_with_progress<F, P, U, E>(self, mut f: F, mut on_progress: P) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+        P: FnMut(usize, usize),
+    {
+        self.try_map_indexed(|index, src| {
+            let dst = f(src)?;
+            on_progress(index, N);
+            Ok(dst)
+        })
+    }
+
+    fn try_map_errors_into<F, U, E>(
+        self,
+        mut f: F,
+        errors: &mut [MaybeUninit<(usize, E)>],
+    ) -> Result<[U; N], usize>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let mut recorded = 0;
+        init::init_with_writer(|dst| {
+            let mut failed = false;
+            for (index, src) in IntoIterator::into_iter(array).enumerate() {
+                match f(src) {
+                    Ok(value) => {
+                        if !failed {
+                            dst.write_next(value);
+                        }
+                    }
+                    Err(error) => {
+                        failed = true;
+                        if let Some(slot) = errors.get_mut(recorded) {
+                            slot.write((index, error));
+                            recorded += 1;
+                        }
+                    }
+                }
+            }
+            if failed {
+                Err(())
+            } else {
+                Ok(())
+            }
+        })
+        .map_err(|()| recorded)
+    }
+
+    fn try_map_unchecked<F, U, E>(self, mut f: F) -> Result<[U; N], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let mut dst = MaybeUninit::<[U; N]>::uninit();
+        let ptr = dst.as_mut_ptr().cast::<U>();
+        for (index, src) in IntoIterator::into_iter(array).enumerate() {
+            match f(src) {
+                // SAFETY: `index` is less than `N`, so the slot is inside the array.
+                Ok(value) => unsafe { ptr.add(index).write(value) },
+                Err(error) => {
+                    // SAFETY: the elements before `index` are initialized and won't be used again.
+                    unsafe {
+                        core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(ptr, index));
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        // SAFETY: every element has been initialized.
+        Ok(unsafe { dst.assume_init() })
+    }
+
+    fn map_unchecked<F, U>(self, mut f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U,
+    {
+        self.try_map_unchecked::<_, _, !>(|src| Ok(f(src)))
+            .into_ok()
+    }
+}
+
+/// Extension of `[T; N]` with methods that combine it with a second array element by element.
+pub trait ArrayZipExt<T, const N: usize> {
+    /// Combines this array with another one of the same length into an array of pairs.
+    ///
+    /// This is the inverse of [`unzip`](crate::UnzipArrayExt::unzip).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{ArrayExt, UnzipArrayExt};
+    /// let pairs = ["a", "b"].zip([1, 2]);
+    /// assert_eq!(pairs, [("a", 1), ("b", 2)]);
+    /// assert_eq!(pairs.unzip(), (["a", "b"], [1, 2]));
+    /// ```
+    fn zip<U>(self, other: [U; N]) -> [(T, U); N];
+
+    /// Combines this array with another one of the same length element by element.
+    ///
+    /// For more than two arrays, see [`zip_map!`](crate::zip_map).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements and the remaining elements of both arrays
+    /// will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let position = [1.0, 2.0, 3.0];
+    /// let velocity = [0.5, 0.0, -1.0];
+    /// assert_eq!(position.zip_with(velocity, |p, v| p + v), [1.5, 2.0, 2.0]);
+    /// ```
+    fn zip_with<U, V, F>(self, other: [U; N], f: F) -> [V; N]
+    where
+        F: FnMut(T, U) -> V;
+
+    /// Fallible version of [`zip_with`](ArrayZipExt::zip_with).
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements and the remaining elements of both arrays
+    /// will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements and the remaining elements of both arrays
+    /// will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let balances = [100u32, 50, 10];
+    /// let withdrawals = [30u32, 50, 20];
+    ///
+    /// let res = balances.try_zip_with(withdrawals, |b, w| b.checked_sub(w).ok_or("insufficient funds"));
+    /// assert_eq!(res, Err("insufficient funds"));
+    ///
+    /// let res = balances.try_zip_with([0, 0, 10], |b, w| b.checked_sub(w).ok_or("insufficient funds"));
+    /// assert_eq!(res, Ok([100, 50, 0]));
+    /// ```
+    fn try_zip_with<U, V, E, F>(self, other: [U; N], f: F) -> Result<[V; N], E>
+    where
+        F: FnMut(T, U) -> Result<V, E>;
+}
+
+impl<Arr, T, const N: usize> ArrayZipExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn zip<U>(self, other: [U; N]) -> [(T, U); N] {
+        self.zip_with(other, |a, b| (a, b))
+    }
+
+    fn zip_with<U, V, F>(self, other: [U; N], mut f: F) -> [V; N]
+    where
+        F: FnMut(T, U) -> V,
+    {
+        self.try_zip_with::<_, _, !, _>(other, |a, b| Ok(f(a, b)))
+            .into_ok()
+    }
+
+    fn try_zip_with<U, V, E, F>(self, other: [U; N], mut f: F) -> Result<[V; N], E>
+    where
+        F: FnMut(T, U) -> Result<V, E>,
+    {
+        let array = AsArray::into_array(self);
+        let mut iter = IntoIterator::into_iter(array).zip(other);
+        init::try_init_array(|_| {
+            let (a, b) = iter.next().unwrap();
+            f(a, b)
+        })
+    }
+}
+
+/// Extension of `[T; N]` with methods that map windows of neighboring elements.
+pub trait ArrayWindowExt<T, const N: usize> {
+    /// Slides `kernel` over the array and folds every window together with it.
+    ///
+    /// For each of the `M` windows of `K` consecutive elements, the fold starts with a clone of `init`
+    /// and calls `f` with the accumulator, an element of the window and the matching element of the kernel.
+    /// The output length `M` is usually inferred, and must be `N - K + 1`,
+    /// which is checked at compile time along with `K` not being zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let signal = [1, 2, 3, 4, 5];
+    /// let smoothed: [i32; 3] = signal.convolve([1, 2, 1], 0, |acc, x, k| acc + x * k);
+    /// assert_eq!(smoothed, [8, 12, 16]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ArrayExt;
+    /// let x: [i32; 3] = [1, 2, 3].convolve([1, 1], 0, |acc, x, k| acc + x * k);
+    /// ```
+    fn convolve<const K: usize, const M: usize, F, U>(
+        &self,
+        kernel: [T; K],
+        init: U,
+        f: F,
+    ) -> [U; M]
+    where
+        F: FnMut(U, &T, &T) -> U,
+        U: Clone;
+
+    /// Fallible version of [`convolve`](ArrayWindowExt::convolve),
+    /// for example to use checked arithmetic on integers.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let checked = |acc: u8, x: &u8, k: &u8| x.checked_mul(*k).and_then(|p| acc.checked_add(p)).ok_or("overflow");
+    ///
+    /// let sums: Result<[u8; 2], _> = [10, 20, 30].try_convolve([1, 2], 0, checked);
+    /// assert_eq!(sums, Ok([50, 80]));
+    ///
+    /// let sums: Result<[u8; 2], _> = [10, 200, 30].try_convolve([1, 2], 0, checked);
+    /// assert_eq!(sums, Err("overflow"));
+    /// ```
+    fn try_convolve<const K: usize, const M: usize, F, U, E>(
+        &self,
+        kernel: [T; K],
+        init: U,
+        f: F,
+    ) -> Result<[U; M], E>
+    where
+        F: FnMut(U, &T, &T) -> Result<U, E>,
+        U: Clone;
+
+    /// Maps every pair of consecutive elements, such as to compute the differences between them.
+    ///
+    /// The output length `M` is usually inferred, and must be `N - 1`,
+    /// which is checked at compile time along with `N` not being zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let timestamps = [100, 150, 175, 300];
+    /// let deltas: [i32; 3] = timestamps.adjacent_map(|a, b| b - a);
+    /// assert_eq!(deltas, [50, 25, 125]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ArrayExt;
+    /// let deltas: [i32; 3] = [1, 2, 3].adjacent_map(|a, b| b - a);
+    /// ```
+    fn adjacent_map<const M: usize, F, U>(self, f: F) -> [U; M]
+    where
+        F: FnMut(&T, &T) -> U;
+
+    /// Fallible version of [`adjacent_map`](ArrayWindowExt::adjacent_map).
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let readings = [10u32, 12, 15];
+    /// let deltas: Result<[u32; 2], _> = readings.try_adjacent_map(|a, b| b.checked_sub(*a).ok_or("decreasing"));
+    /// assert_eq!(deltas, Ok([2, 3]));
+    ///
+    /// let readings = [10u32, 12, 11];
+    /// let deltas: Result<[u32; 2], _> = readings.try_adjacent_map(|a, b| b.checked_sub(*a).ok_or("decreasing"));
+    /// assert_eq!(deltas, Err("decreasing"));
+    /// ```
+    fn try_adjacent_map<const M: usize, F, U, E>(self, f: F) -> Result<[U; M], E>
+    where
+        F: FnMut(&T, &T) -> Result<U, E>;
+}
+
+impl<Arr, T, const N: usize> ArrayWindowExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn convolve<const K: usize, const M: usize, F, U>(
+        &self,
+        kernel: [T; K],
+        init: U,
+        mut f: F,
+    ) -> [U; M]
+    where
+        F: FnMut(U, &T, &T) -> U,
+        U: Clone,
+    {
+        self.try_convolve::<K, M, _, _, !>(kernel, init, |acc, src, k| Ok(f(acc, src, k)))
+            .into_ok()
+    }
+
+    fn try_convolve<const K: usize, const M: usize, F, U, E>(
+        &self,
+        kernel: [T; K],
+        init: U,
+        mut f: F,
+    ) -> Result<[U; M], E>
+    where
+        F: FnMut(U, &T, &T) -> Result<U, E>,
+        U: Clone,
+    {
+        let array = AsArray::as_array(self);
+        let () = assert::AssertWindows::<N, K, M>::OK;
+        let mut start = 0;
+        [(); M].try_map(|()| {
+            let window = &array[start..start + K];
+            start += 1;
+            window
+                .iter()
+                .zip(&kernel)
+                .try_fold(init.clone(), |acc, (src, k)| f(acc, src, k))
+        })
+    }
+
+    fn adjacent_map<const M: usize, F, U>(self, mut f: F) -> [U; M]
+    where
+        F: FnMut(&T, &T) -> U,
+    {
+        self.try_adjacent_map::<M, _, _, !>(|a, b| Ok(f(a, b)))
+            .into_ok()
+    }
+
+    fn try_adjacent_map<const M: usize, F, U, E>(self, mut f: F) -> Result<[U; M], E>
+    where
+        F: FnMut(&T, &T) -> Result<U, E>,
+    {
+        let array = AsArray::into_array(self);
+        let () = assert::AssertWindows::<N, 2, M>::OK;
+        init::try_init_array(|index| f(&array[index], &array[index + 1]))
+    }
+}
+
+/// Extension of `[T; N]` with methods that convert every element to another type.
+pub trait ArrayConvertExt<T, const N: usize> {
+    /// Converts every element into `U` using its [`From`] implementation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `U::from` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x: [u8; 3] = [1, 2, 255];
+    /// let y = x.map_into::<u32>();
+    /// assert_eq!(y, [1, 2, 255]);
+    ///
+    /// let x = ["a", "b"];
+    /// let y: [String; 2] = x.map_into();
+    /// assert_eq!(y, ["a".to_string(), "b".to_string()]);
+    /// ```
+    fn map_into<U>(self) -> [U; N]
+    where
+        U: From<T>;
+
+    /// Converts every element into the primitive type `U` as if by an `as` cast.
+    ///
+    /// Like `as`, this may truncate, wrap or lose precision.
+    /// See [`AsPrimitive`] for the supported types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x: [u32; 3] = [1, 256, 257];
+    /// let y = x.map_as::<u8>();
+    /// assert_eq!(y, [1, 0, 1]);
+    ///
+    /// let x = [1.5f32, -2.7, 300.0];
+    /// let y = x.map_as::<i8>();
+    /// assert_eq!(y, [1, -2, 127]);
+    /// ```
+    fn map_as<U>(self) -> [U; N]
+    where
+        T: AsPrimitive<U>;
+
+    /// Converts every element into `U` using its [`TryFrom`] implementation.
+    /// Conversion stops at the first element that fails.
+    ///
+    /// # Errors
+    ///
+    /// If `U::try_from` fails, the error will be returned together with the index of the element that failed.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `U::try_from` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x: [i32; 3] = [1, 2, 3];
+    /// let y = x.try_map_into::<u8>();
+    /// assert_eq!(y, Ok([1, 2, 3]));
+    ///
+    /// let x: [i32; 4] = [1, 2, -3, 300];
+    /// let err = x.try_map_into::<u8>().unwrap_err();
+    /// assert_eq!(err.index, 2);
+    /// ```
+    fn try_map_into<U>(self) -> Result<[U; N], TryMapError<U::Error>>
+    where
+        U: TryFrom<T>;
+
+    /// Converts every element into `U` using its [`TryFrom`] implementation.
+    /// This is a shorthand for `try_map(U::try_from)`.
+    ///
+    /// Unlike [`try_map_into`](ArrayConvertExt::try_map_into), the error isn't wrapped with the index of the element that failed.
+    ///
+    /// # Errors
+    ///
+    /// If `U::try_from` fails, that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `U::try_from` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x: [i64; 3] = [1, 2, 65535];
+    /// assert_eq!(x.try_cast::<u16>(), Ok([1, 2, 65535]));
+    ///
+    /// let x: [i64; 3] = [1, -2, 65536];
+    /// assert!(x.try_cast::<u16>().is_err());
+    /// ```
+    fn try_cast<U>(self) -> Result<[U; N], U::Error>
+    where
+        U: TryFrom<T>;
+
+    /// Converts every element into the primitive type `U`, failing if a value is out of range.
+    /// This is the checked counterpart of [`map_as`](ArrayConvertExt::map_as).
+    /// See [`CheckedCast`] for the exact rules.
+    ///
+    /// # Errors
+    ///
+    /// If an element does not fit into `U`, a [`CastError`] will be returned together with its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x: [i64; 3] = [1, 2, 3];
+    /// let y = x.checked_cast::<u8>();
+    /// assert_eq!(y, Ok([1, 2, 3]));
+    ///
+    /// let x = [1.5f64, -1.0, f64::NAN];
+    /// let err = x.checked_cast::<u32>().unwrap_err();
+    /// assert_eq!(err.index, 1);
+    /// ```
+    fn checked_cast<U>(self) -> Result<[U; N], TryMapError<CastError>>
+    where
+        T: CheckedCast<U>;
+
+    /// Wraps every element in the newtype `W` without moving them individually.
+    /// This is the inverse of [`peel`](ArrayConvertExt::peel).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{ArrayExt, TransparentWrapper};
+    /// #[derive(Debug, PartialEq)]
+    /// #[repr(transparent)]
+    /// struct Id(u32);
+    ///
+    /// unsafe impl TransparentWrapper<u32> for Id {}
+    ///
+    /// let x = [1, 2, 3];
+    /// let y = x.wrap::<Id>();
+    /// assert_eq!(y, [Id(1), Id(2), Id(3)]);
+    /// ```
+    fn wrap<W>(self) -> [W; N]
+    where
+        W: TransparentWrapper<T>;
+
+    /// Unwraps every element from its newtype without moving them individually.
+    /// This is the inverse of [`wrap`](ArrayConvertExt::wrap).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{ArrayExt, TransparentWrapper};
+    /// #[repr(transparent)]
+    /// struct Id(u32);
+    ///
+    /// unsafe impl TransparentWrapper<u32> for Id {}
+    ///
+    /// let x = [Id(1), Id(2), Id(3)];
+    /// let y: [u32; 3] = x.peel();
+    /// assert_eq!(y, [1, 2, 3]);
+    /// ```
+    fn peel<U>(self) -> [U; N]
+    where
+        T: TransparentWrapper<U>;
+
+    /// Splits an array of structs into a struct of arrays.
+    /// This is a shorthand for [`StructOfArrays::into_soa`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{impl_soa, ArrayExt};
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// impl_soa! {
+    ///     Point => struct Points {
+    ///         x: i32,
+    ///         y: i32,
+    ///     }
+    /// }
+    ///
+    /// let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }].into_soa();
+    /// assert_eq!(points.x, [1, 3]);
+    /// assert_eq!(points.y, [2, 4]);
+    /// ```
+    fn into_soa(self) -> T::Arrays
+    where
+        T: StructOfArrays<N>;
+
+    /// Checks that every element is a valid index into an array of length `L`.
+    /// The resulting [`Idx`]s can then be used repeatedly without bounds checks.
+    ///
+    /// # Errors
+    ///
+    /// If an element is not less than `L`, an [`OutOfBounds`] error describing the first such element will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let table = [2usize, 0, 1];
+    /// let perm = table.try_into_indices::<3>().unwrap();
+    ///
+    /// let x = ["a", "b", "c"];
+    /// assert_eq!(perm.map(|i| x[i]), ["c", "a", "b"]);
+    ///
+    /// let err = [0usize, 3].try_into_indices::<3>().unwrap_err();
+    /// assert_eq!((err.position, err.index), (1, 3));
+    /// ```
+    fn try_into_indices<const L: usize>(self) -> Result<[Idx<L>; N], OutOfBounds>
+    where
+        T: Into<usize>;
+}
+
+impl<Arr, T, const N: usize> ArrayConvertExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn map_into<U>(self) -> [U; N]
+    where
+        U: From<T>,
+    {
+        self.map2(U::from)
+    }
+
+    fn map_as<U>(self) -> [U; N]
+    where
+        T: AsPrimitive<U>,
+    {
+        self.map2(AsPrimitive::as_)
+    }
+
+    fn try_cast<U>(self) -> Result<[U; N], U::Error>
+    where
+        U: TryFrom<T>,
+    {
+        self.try_map(U::try_from)
+    }
+
+    fn try_map_into<U>(self) -> Result<[U; N], TryMapError<U::Error>>
+    where
+        U: TryFrom<T>,
+    {
+        self.try_map_indexed(|index, src| {
+            U::try_from(src).map_err(|error| TryMapError { index, error })
+        })
+    }
+
+    fn checked_cast<U>(self) -> Result<[U; N], TryMapError<CastError>>
+    where
+        T: CheckedCast<U>,
+    {
+        self.try_map_indexed(|index, src| {
+            src.checked_cast().ok_or(TryMapError {
+                index,
+                error: CastError,
+            })
+        })
+    }
+
+    fn wrap<W>(self) -> [W; N]
+    where
+        W: TransparentWrapper<T>,
+    {
+        let array = AsArray::into_array(self);
+        // SAFETY: `W` is a transparent wrapper around `T`, so they have the same layout.
+        unsafe { newtype::reinterpret(array) }
+    }
+
+    fn peel<U>(self) -> [U; N]
+    where
+        T: TransparentWrapper<U>,
+    {
+        let array = AsArray::into_array(self);
+        // SAFETY: `T` is a transparent wrapper around `U`, so they have the same layout.
+        unsafe { newtype::reinterpret(array) }
+    }
+
+    fn into_soa(self) -> T::Arrays
+    where
+        T: StructOfArrays<N>,
+    {
+        let array = AsArray::into_array(self);
+        T::into_soa(array)
+    }
+
+    fn try_into_indices<const L: usize>(self) -> Result<[Idx<L>; N], OutOfBounds>
+    where
+        T: Into<usize>,
+    {
+        self.try_map_indexed(|position, src| {
+            let index = src.into();
+            Idx::new(index).ok_or(OutOfBounds {
+                position,
+                index,
+                len: L,
+            })
+        })
+    }
+}
+
+/// Extension of `[T; N]` with methods that fold, search or update the elements instead of mapping them.
+pub trait ArrayFoldExt<T, const N: usize> {
+    /// Folds the elements of the array into an accumulator by value, in order,
+    /// until the array ends or an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The elements that haven't been passed to `f` will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The elements that haven't been passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let parts = [String::from("a"), String::from("b"), String::from("c")];
+    /// let joined = parts.try_fold(String::new(), |mut acc, part| {
+    ///     if acc.len() + part.len() > 8 {
+    ///         return Err("too long");
+    ///     }
+    ///     acc.push_str(&part);
+    ///     Ok(acc)
+    /// });
+    /// assert_eq!(joined.as_deref(), Ok("abc"));
+    /// ```
+    fn try_fold<B, F, E>(self, init: B, f: F) -> Result<B, E>
+    where
+        F: FnMut(B, T) -> Result<B, E>;
+
+    /// Runs a fallible function on every element by value, in order,
+    /// until the array ends or an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The elements that haven't been passed to `f` will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The elements that haven't been passed to `f` will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut sent = Vec::new();
+    /// let res = ["ping", "stop", "pong"].try_for_each(|cmd| {
+    ///     if cmd == "stop" {
+    ///         return Err("stopped");
+    ///     }
+    ///     sent.push(cmd);
+    ///     Ok(())
+    /// });
+    /// assert_eq!(res, Err("stopped"));
+    /// assert_eq!(sent, ["ping"]);
+    /// ```
+    fn try_for_each<F, E>(self, f: F) -> Result<(), E>
+    where
+        F: FnMut(T) -> Result<(), E>;
+
+    /// Runs a fallible function on a mutable reference to every element, in order,
+    /// until the array ends or an error is returned.
+    /// This mutates the array in place, so unlike [`try_map`](ArrayMapExt::try_map),
+    /// no new array is built.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// Only the elements before the failing one and the failing element itself
+    /// may have been mutated; the elements after it are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut x = [1u8, 2, 3];
+    /// assert_eq!(x.try_for_each_mut(|v| { *v *= 10; Ok::<_, ()>(()) }), Ok(()));
+    /// assert_eq!(x, [10, 20, 30]);
+    ///
+    /// let mut x = [100u8, 200, 50];
+    /// let res = x.try_for_each_mut(|v| {
+    ///     *v = v.checked_add(100).ok_or("overflow")?;
+    ///     Ok(())
+    /// });
+    /// assert_eq!(res, Err("overflow"));
+    /// assert_eq!(x, [200, 200, 50]);
+    /// ```
+    #[doc(alias = "try_map_mut")]
+    fn try_for_each_mut<F, E>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<(), E>;
+
+    /// Swaps the elements of two arrays pairwise, in order,
+    /// after checking each pair with a fallible function.
+    ///
+    /// # Errors
+    ///
+    /// If `f` rejects a pair by returning an [`Err`], that error will be returned by this function.
+    /// The pairs before the rejected one have been swapped,
+    /// while the rejected pair and all pairs after it are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut front = [1, 2, 3];
+    /// let mut back = [4, 5, 6];
+    /// assert_eq!(front.try_swap_with(&mut back, |_, _| Ok::<_, ()>(())), Ok(()));
+    /// assert_eq!((front, back), ([4, 5, 6], [1, 2, 3]));
+    ///
+    /// let mut front = [1, 2, 3];
+    /// let mut back = [4, -5, 6];
+    /// let res = front.try_swap_with(&mut back, |_, b| if *b < 0 { Err(*b) } else { Ok(()) });
+    /// assert_eq!(res, Err(-5));
+    /// assert_eq!((front, back), ([4, 2, 3], [1, -5, 6]));
+    /// ```
+    fn try_swap_with<F, E>(&mut self, other: &mut [T; N], f: F) -> Result<(), E>
+    where
+        F: FnMut(&T, &T) -> Result<(), E>;
+
+    /// Replaces every element in place with the result of passing it to `f` by value.
+    ///
+    /// Unlike [`core::mem::take`] or [`core::mem::replace`], this doesn't require a placeholder value,
+    /// so `T` doesn't need to implement [`Default`] or [`Clone`].
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics, the element it received is gone and there is nothing left to put in its place,
+    /// so the process is aborted instead of unwinding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// enum State {
+    ///     Idle(u32),
+    ///     Running(u32),
+    /// }
+    ///
+    /// let mut states = [State::Idle(1), State::Running(2)];
+    /// states.replace_map(|s| match s {
+    ///     State::Idle(n) => State::Running(n),
+    ///     State::Running(n) => State::Idle(n + 1),
+    /// });
+    /// assert!(matches!(states, [State::Running(1), State::Idle(3)]));
+    /// ```
+    fn replace_map<F>(&mut self, f: F)
+    where
+        F: FnMut(T) -> T;
+
+    /// Binary searches this sorted array with a comparator that can fail.
+    ///
+    /// Like [`slice::binary_search_by`], `f` should return whether the element is less than,
+    /// equal to or greater than the target.
+    /// If the target is found, [`Ok`] with its index is returned, and if there are multiple matches,
+    /// any of them may be returned.
+    /// Otherwise, [`Err`] with the index where the target could be inserted while keeping the order is returned.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], the search stops and that error will be returned by this function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let table = ["10", "20", "30", "40"];
+    /// let find = |target: u32| {
+    ///     table.try_binary_search_by(|key| key.parse::<u32>().map(|key| key.cmp(&target)))
+    /// };
+    ///
+    /// assert_eq!(find(30), Ok(Ok(2)));
+    /// assert_eq!(find(25), Ok(Err(2)));
+    ///
+    /// let table = ["10", "2O", "30"];
+    /// assert!(table.try_binary_search_by(|key| key.parse::<u32>().map(|key| key.cmp(&20))).is_err());
+    /// ```
+    fn try_binary_search_by<F, E>(&self, f: F) -> Result<Result<usize, usize>, E>
+    where
+        F: FnMut(&T) -> Result<Ordering, E>;
+
+    /// Compresses the array into runs of equal consecutive elements, each stored with its length.
+    /// The first element of each run is kept, and the others are dropped.
+    ///
+    /// The runs can be expanded back into an array with [`run_length_decode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let runs = ['a', 'a', 'b', 'c', 'c', 'c'].run_length_encode();
+    /// assert_eq!(runs, [('a', 2), ('b', 1), ('c', 3)]);
+    /// assert_eq!(runs.capacity(), 6);
+    /// ```
+    fn run_length_encode(self) -> ArrayVec<(T, usize), N>
+    where
+        T: PartialEq;
+}
+
+impl<Arr, T, const N: usize> ArrayFoldExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn try_fold<B, F, E>(self, init: B, f: F) -> Result<B, E>
+    where
+        F: FnMut(B, T) -> Result<B, E>,
+    {
+        let array = AsArray::into_array(self);
+        IntoIterator::into_iter(array).try_fold(init, f)
+    }
+
+    fn try_for_each<F, E>(self, f: F) -> Result<(), E>
+    where
+        F: FnMut(T) -> Result<(), E>,
+    {
+        let array = AsArray::into_array(self);
+        IntoIterator::into_iter(array).try_for_each(f)
+    }
+
+    fn try_for_each_mut<F, E>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<(), E>,
+    {
+        let array = AsArray::as_mut_array(self);
+        array.iter_mut().try_for_each(f)
+    }
+
+    fn try_swap_with<F, E>(&mut self, other: &mut [T; N], mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&T, &T) -> Result<(), E>,
+    {
+        let array = AsArray::as_mut_array(self);
+        for (a, b) in array.iter_mut().zip(other) {
+            f(a, b)?;
+            core::mem::swap(a, b);
+        }
+        Ok(())
+    }
+
+    fn replace_map<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> T,
+    {
+        struct AbortOnUnwind;
+
+        impl Drop for AbortOnUnwind {
+            fn drop(&mut self) {
+                // Panicking while already unwinding aborts the process.
+                panic!("the function passed to `replace_map` panicked");
+            }
+        }
+
+        for elem in AsArray::as_mut_array(self).iter_mut() {
+            let guard = AbortOnUnwind;
+            // SAFETY: the element is moved out and written back before anyone can observe it.
+            // If `f` panics, the guard aborts the process before the moved out element can be used again.
+            unsafe {
+                let value = core::ptr::read(elem);
+                core::ptr::write(elem, f(value));
+            }
+            core::mem::forget(guard);
+        }
+    }
+
+    fn try_binary_search_by<F, E>(&self, mut f: F) -> Result<Result<usize, usize>, E>
+    where
+        F: FnMut(&T) -> Result<Ordering, E>,
+    {
+        let array = AsArray::as_array(self);
+        let mut left = 0;
+        let mut right = N;
+        while left < right {
+            let mid = left + (right - left) / 2;
+            match f(&array[mid])? {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(Ok(mid)),
+            }
+        }
+        Ok(Err(left))
+    }
+
+    fn run_length_encode(self) -> ArrayVec<(T, usize), N>
+    where
+        T: PartialEq,
+    {
+        let array = AsArray::into_array(self);
+        let mut runs = ArrayVec::new();
+        let mut current: Option<(T, usize)> = None;
+        for elem in IntoIterator::into_iter(array) {
+            match &mut current {
+                Some((value, count)) if *value == elem => *count += 1,
+                _ => {
+                    if let Some(run) = current.replace((elem, 1)) {
+                        runs.push(run);
+                    }
+                }
+            }
+        }
+        if let Some(run) = current {
+            runs.push(run);
+        }
+        runs
+    }
+}
+
+/// Extension of `[T; N]` with methods that move the elements to other positions or into several arrays.
+pub trait ArrayReorderExt<T, const N: usize> {
+    /// Splits the array into three consecutive arrays of lengths `A`, `B` and `C`.
+    /// The elements are moved, not copied.
+    ///
+    /// Using lengths that don't add up to `N` is a compile-time error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let packet = [0xAA, 0x01, 0x02, 0x03, 0x04, 0xFF];
+    /// let (header, body, footer) = packet.split_ranges::<1, 4, 1>();
+    /// assert_eq!(header, [0xAA]);
+    /// assert_eq!(body, [0x01, 0x02, 0x03, 0x04]);
+    /// assert_eq!(footer, [0xFF]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ArrayExt;
+    /// let (a, b, c) = [1, 2, 3].split_ranges::<1, 1, 2>();
+    /// ```
+    fn split_ranges<const A: usize, const B: usize, const C: usize>(
+        self,
+    ) -> ([T; A], [T; B], [T; C]);
+
+    /// Returns the elements in reverse order.
+    /// The elements are moved, not cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x = [String::from("a"), String::from("b"), String::from("c")];
+    /// assert_eq!(x.reversed(), ["c", "b", "a"]);
+    /// ```
+    fn reversed(self) -> [T; N];
+
+    /// Returns the elements rotated `k` places to the left,
+    /// so that the element at index `k` becomes the first one.
+    /// The elements are moved, not cloned.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `k` is greater than `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x = [String::from("a"), String::from("b"), String::from("c")];
+    /// assert_eq!(x.rotated_left(1), ["b", "c", "a"]);
+    /// ```
+    fn rotated_left(self, k: usize) -> [T; N];
+
+    /// Returns the elements rotated `k` places to the right,
+    /// so that the element at index `N - k` becomes the first one.
+    /// The elements are moved, not cloned.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `k` is greater than `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let x = [String::from("a"), String::from("b"), String::from("c")];
+    /// assert_eq!(x.rotated_right(1), ["c", "a", "b"]);
+    /// ```
+    fn rotated_right(self, k: usize) -> [T; N];
+
+    /// Returns the elements in a random order, chosen with `rng`.
+    /// The elements are moved, not cloned.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `rng` returns an index that is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// // A tiny linear congruential generator, use a real one in practice.
+    /// let mut state = 7u64;
+    /// let mut rng = |len: usize| {
+    ///     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    ///     (state >> 33) as usize % len
+    /// };
+    ///
+    /// let mut deck = [1, 2, 3, 4, 5].shuffled(&mut rng);
+    /// deck.sort();
+    /// assert_eq!(deck, [1, 2, 3, 4, 5]);
+    /// ```
+    fn shuffled<R>(self, rng: &mut R) -> [T; N]
+    where
+        R: RandomIndex + ?Sized;
+
+    /// Picks `M` distinct elements at random, chosen with `rng`, and returns them in a random order.
+    /// The picked elements are moved, not cloned, and the others are dropped.
+    ///
+    /// Picking more elements than the array has is a compile-time error.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `rng` returns an index that is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ArrayExt;
+    /// let mut rng = |len: usize| len - 1;
+    /// let [a, b] = [10, 20, 30, 40].sample::<2, _>(&mut rng);
+    /// assert_ne!(a, b);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ArrayExt;
+    /// let mut rng = |_: usize| 0;
+    /// let x: [u8; 4] = [1, 2, 3].sample(&mut rng);
+    /// ```
+    fn sample<const M: usize, R>(self, rng: &mut R) -> [T; M]
+    where
+        R: RandomIndex + ?Sized;
+}
+
+impl<Arr, T, const N: usize> ArrayReorderExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn split_ranges<const A: usize, const B: usize, const C: usize>(
+        self,
+    ) -> ([T; A], [T; B], [T; C]) {
+        let array = AsArray::into_array(self);
+        let () = assert::AssertSum3::<A, B, C, N>::OK;
+        let mut iter = IntoIterator::into_iter(array);
+        let a = [(); A].map2(|()| iter.next().unwrap());
+        let b = [(); B].map2(|()| iter.next().unwrap());
+        let c = [(); C].map2(|()| iter.next().unwrap());
+        (a, b, c)
+    }
+
+    fn reversed(self) -> [T; N] {
+        let mut array = AsArray::into_array(self);
+        array.reverse();
+        array
+    }
+
+    fn rotated_left(self, k: usize) -> [T; N] {
+        let mut array = AsArray::into_array(self);
+        array.rotate_left(k);
+        array
+    }
+
+    fn rotated_right(self, k: usize) -> [T; N] {
+        let mut array = AsArray::into_array(self);
+        array.rotate_right(k);
+        array
+    }
+
+    fn shuffled<R>(self, rng: &mut R) -> [T; N]
     where
-        F: FnMut(T) -> Result<U, E>,
+        R: RandomIndex + ?Sized,
     {
-        use core::mem::MaybeUninit;
-        struct Guard<T, const N: usize> {
-            dst: *mut T,
-            initialized: usize,
-        }
-
-        impl<T, const N: usize> Drop for Guard<T, N> {
-            fn drop(&mut self) {
-                debug_assert!(self.initialized <= N);
-
-                let initialized_part =
-                    core::ptr::slice_from_raw_parts_mut(self.dst, self.initialized);
-                // SAFETY: this raw slice will contain only initialized objects
-                // that's why, it is allowed to drop it.
-                unsafe {
-                    core::ptr::drop_in_place(initialized_part);
-                }
-            }
-        }
-        let mut dst = MaybeUninit::uninit_array::<N>();
-        let mut guard: Guard<U, N> = Guard {
-            dst: MaybeUninit::slice_as_mut_ptr(&mut dst),
-            initialized: 0,
-        };
-        for (src, dst) in core::array::IntoIter::new(self).zip(&mut dst) {
-            //CHANGED FROM CORE: match on `f(src)` instead of directly inputting it into `dst.write`
-            match f(src) {
-                Ok(elem) => {
-                    dst.write(elem);
-                    guard.initialized += 1;
-                }
-                Err(err) => return Err(err),
-            }
-        }
-        // FIXME: Convert to crate::mem::transmute once it works with generics.
-        // unsafe { crate::mem::transmute::<[MaybeUninit<U>; N], [U; N]>(dst) }
-        core::mem::forget(guard);
-        // SAFETY: At this point we've properly initialized the whole array
-        // and we just need to cast it to the correct type.
-        Ok(unsafe { core::mem::transmute_copy::<_, [U; N]>(&dst) }) //CHANGED FROM CORE: Ok-wrapped
+        let mut array = AsArray::into_array(self);
+        random::partial_shuffle(&mut array, N, rng);
+        array
     }
 
-    fn map2<F, U>(self, mut f: F) -> [U; N]
+    fn sample<const M: usize, R>(self, rng: &mut R) -> [T; M]
     where
-        F: FnMut(T) -> U,
+        R: RandomIndex + ?Sized,
     {
-        self.try_map::<_, _, !>(|src| Ok(f(src))).into_ok()
+        let mut array = AsArray::into_array(self);
+        let () = assert::AssertLe::<M, N>::OK;
+        random::partial_shuffle(&mut array, M, rng);
+        let mut iter = IntoIterator::into_iter(array);
+        [(); M].map2(|()| iter.next().unwrap())
     }
 }
 
+/// Runs `f` for the element at `index`, reporting it to the hook of the `trace` feature if it is enabled.
+#[inline(always)]
+fn traced<U, E>(index: usize, f: impl FnOnce() -> Result<U, E>) -> Result<U, E> {
+    #[cfg(feature = "trace")]
+    trace::emit(index, trace::Phase::Start);
+    let res = f();
+    #[cfg(feature = "trace")]
+    trace::emit_result(index, &res);
+    #[cfg(not(feature = "trace"))]
+    let _ = index;
+    res
+}
+
+/// Infallible version of [`traced`].
+#[inline(always)]
+fn traced_infallible<U>(index: usize, f: impl FnOnce() -> U) -> U {
+    #[cfg(feature = "trace")]
+    trace::emit(index, trace::Phase::Start);
+    let value = f();
+    #[cfg(feature = "trace")]
+    trace::emit(index, trace::Phase::Ok);
+    #[cfg(not(feature = "trace"))]
+    let _ = index;
+    value
+}
+
 #[cfg(test)]
 mod test {
     extern crate std;
@@ -140,9 +2191,12 @@ mod test {
     use super::ArrayExt;
 
     use std::{
+        future::Future,
         mem, panic,
+        pin::Pin,
         rc::Rc,
         sync::atomic::{AtomicUsize, Ordering},
+        task::{Context, Poll, Waker},
     };
 
     #[test]
@@ -231,4 +2285,604 @@ mod test {
 
         assert_eq!(counter, 2);
     }
+
+    /// Tests the range checks of float casts at the edges of the target type.
+    #[test]
+    fn checked_cast_bounds() {
+        use super::CheckedCast;
+
+        assert_eq!(CheckedCast::<u8>::checked_cast(255.9f32), Some(255));
+        assert_eq!(CheckedCast::<u8>::checked_cast(256.0f32), None);
+        assert_eq!(CheckedCast::<u8>::checked_cast(-0.9f32), Some(0));
+        assert_eq!(CheckedCast::<u8>::checked_cast(-1.0f32), None);
+        assert_eq!(CheckedCast::<i8>::checked_cast(-128.9f64), Some(-128));
+        assert_eq!(CheckedCast::<i8>::checked_cast(-129.0f64), None);
+        assert_eq!(
+            CheckedCast::<i64>::checked_cast(-9223372036854775808.0f64),
+            Some(i64::MIN)
+        );
+        assert_eq!(
+            CheckedCast::<i64>::checked_cast(9223372036854775808.0f64),
+            None
+        );
+        assert_eq!(
+            CheckedCast::<u128>::checked_cast(f32::MAX),
+            Some(f32::MAX as u128)
+        );
+        assert_eq!(CheckedCast::<u32>::checked_cast(f64::NAN), None);
+        assert_eq!(CheckedCast::<u32>::checked_cast(f64::INFINITY), None);
+        assert_eq!(CheckedCast::<f32>::checked_cast(1e300f64), None);
+        assert_eq!(CheckedCast::<f32>::checked_cast(u128::MAX), None);
+        assert_eq!(
+            CheckedCast::<f64>::checked_cast(u128::MAX),
+            Some(u128::MAX as f64)
+        );
+        assert_eq!(
+            CheckedCast::<f32>::checked_cast(f64::INFINITY),
+            Some(f32::INFINITY)
+        );
+    }
+
+    /// Tests that splitting into and rebuilding from a struct of arrays neither leaks nor double drops.
+    #[test]
+    fn soa_round_trip() {
+        use super::StructOfArrays;
+
+        struct Pair {
+            a: Rc<()>,
+            b: u8,
+        }
+
+        crate::impl_soa! {
+            Pair => struct Pairs {
+                a: Rc<()>,
+                b: u8,
+            }
+        }
+
+        let rc = Rc::new(());
+        let x = [(); 3].map2(|()| Pair {
+            a: rc.clone(),
+            b: 7,
+        });
+        let soa = x.into_soa();
+        assert_eq!(Rc::strong_count(&rc), 4);
+        assert_eq!(soa.b, [7; 3]);
+
+        let y = Pair::from_soa(soa);
+        assert_eq!(Rc::strong_count(&rc), 4);
+        assert!(y.iter().all(|p| Rc::ptr_eq(&p.a, &rc)));
+
+        mem::drop(y);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that unzipping and rebuilding an array of pairs neither leaks nor double drops.
+    #[test]
+    fn unzip_round_trip() {
+        use super::{StructOfArrays, UnzipArrayExt};
+
+        let rc = Rc::new(());
+        let x = [0, 1, 2].map2(|i| (rc.clone(), i));
+        let (a, b) = x.unzip();
+        assert_eq!(Rc::strong_count(&rc), 4);
+        assert_eq!(b, [0, 1, 2]);
+
+        let y = <(Rc<()>, i32)>::from_soa((a, b));
+        assert_eq!(Rc::strong_count(&rc), 4);
+        assert_eq!(y.map2(|(_, i)| i), [0, 1, 2]);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that `zip_map!` drops the built elements and all unconsumed inputs when the body panics.
+    #[test]
+    fn zip_map_drop_on_panic() {
+        let rc = Rc::new(());
+        let a = [(); 4].map2(|()| rc.clone());
+        let b = [(); 4].map2(|()| rc.clone());
+        let c = [0, 0, 255, 0];
+
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+            crate::zip_map!((a, b, c) => |a, b, c| {
+                if c != 0 {
+                    panic!("expected panic");
+                }
+                (a, b)
+            })
+        }));
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that a newtype using `delegate_array_ext!` gets every method, including ones with bounds on the element type.
+    #[test]
+    fn delegate_newtype() {
+        use super::NonEmptyArrayExt;
+
+        struct Bytes([u8; 4]);
+
+        crate::delegate_array_ext!(Bytes => [u8; 4]);
+
+        assert_eq!(Bytes([1, 2, 3, 4]).map2(|v| v * 2), [2, 4, 6, 8]);
+        assert_eq!(Bytes([1, 2, 3, 4]).checked_cast::<i8>(), Ok([1, 2, 3, 4]));
+        let idx = Bytes([3, 2, 1, 0]).try_into_indices::<4>().unwrap();
+        assert_eq!(idx.map(|i| i.get()), [3, 2, 1, 0]);
+        assert_eq!(*Bytes([1, 2, 3, 4]).min_by(|a, b| b.cmp(a)), 4);
+        let mut b = Bytes([1, 2, 3, 4]);
+        b.replace_map(|v| v + 1);
+        assert_eq!(b.0, [2, 3, 4, 5]);
+    }
+
+    /// Tests that `assert_array_eq!` reports the first differing index and the number of differences.
+    #[test]
+    fn assert_array_eq_message() {
+        use std::string::String;
+
+        let mut x = [0u8; 100];
+        x[40] = 1;
+        x[60] = 2;
+
+        let err =
+            panic::catch_unwind(|| crate::assert_array_eq!(x, [0u8; 100], "cleared")).unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+
+        assert!(msg.contains("failed: cleared"));
+        assert!(msg.contains("2 of 100 elements differ, first at index 40"));
+        assert!(msg.contains("left[40]: 1"));
+        assert!(msg.contains("right[40]: 0"));
+    }
+
+    /// Tests that a panic during an in-place map drops both the converted and the remaining elements.
+    #[test]
+    fn map_in_place_transmute_drop_on_panic() {
+        let rc = Rc::new(());
+        let mut count = 0;
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let array: &'static mut [Rc<()>; 4] = std::boxed::Box::leak(std::boxed::Box::new([
+                rc.clone(),
+                rc.clone(),
+                rc.clone(),
+                rc.clone(),
+            ]));
+            super::map_in_place_transmute(array, |x| {
+                count += 1;
+                if count == 3 {
+                    panic!("expected panic");
+                }
+                Some(x)
+            });
+        }));
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that the unchecked maps produce the same results and drop the mapped elements on errors.
+    #[test]
+    fn unchecked_maps() {
+        let rc = Rc::new(());
+
+        let res =
+            [0, 0, 0, 255, 0].try_map_unchecked(|i| if i == 0 { Ok(rc.clone()) } else { Err(i) });
+        assert_eq!(res.unwrap_err(), 255);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let array = [1, 2, 3].map_unchecked(|_| rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 4);
+        drop(array);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let strings = ["a", "bc"].map_unchecked(std::string::String::from);
+        assert_eq!(strings, ["a", "bc"]);
+    }
+
+    /// Tests which elements the unchecked maps drop and which they leak when `f` panics.
+    /// The counters live in statics instead of the heap, so the leaked outputs don't make Miri fail.
+    #[test]
+    fn unchecked_maps_panic() {
+        static INPUTS: AtomicUsize = AtomicUsize::new(0);
+        static OUTPUTS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted(&'static AtomicUsize);
+
+        impl Counted {
+            fn new(counter: &'static AtomicUsize) -> Self {
+                counter.fetch_add(1, Ordering::AcqRel);
+                Self(counter)
+            }
+        }
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::AcqRel);
+            }
+        }
+
+        let inputs = [(); 5].map(|()| Counted::new(&INPUTS));
+        let mut count = 0;
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            inputs.map_unchecked(|_| {
+                count += 1;
+                if count == 3 {
+                    panic!("expected panic");
+                }
+                Counted::new(&OUTPUTS)
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(INPUTS.load(Ordering::Acquire), 0);
+        assert_eq!(OUTPUTS.load(Ordering::Acquire), 2);
+
+        let inputs = [(); 3].map(|()| Counted::new(&INPUTS));
+        let res = inputs.try_map_unchecked(|_| {
+            if OUTPUTS.load(Ordering::Acquire) < 4 {
+                Ok(Counted::new(&OUTPUTS))
+            } else {
+                Err(())
+            }
+        });
+        assert!(res.is_err());
+        assert_eq!(INPUTS.load(Ordering::Acquire), 0);
+        assert_eq!(OUTPUTS.load(Ordering::Acquire), 2);
+    }
+
+    /// Tests that an error during an in-place map drops both the mapped and the remaining elements.
+    #[test]
+    fn map_in_place_drop_on_err() {
+        let rc = Rc::new(());
+        let array = [rc.clone(), rc.clone(), rc.clone(), rc.clone()];
+
+        let mut count = 0;
+        let res = array.try_map_in_place(|x| {
+            count += 1;
+            if count == 2 {
+                Err(())
+            } else {
+                Ok(Some(x))
+            }
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while zipping drops the built elements and the remaining elements of both arrays.
+    #[test]
+    fn try_zip_with_drop_on_err() {
+        let rc = Rc::new(());
+        let a = [rc.clone(), rc.clone(), rc.clone()];
+        let b = [0, 1, 2].map(|_| rc.clone());
+
+        let mut count = 0;
+        let res = a.try_zip_with(b, |a, b| {
+            count += 1;
+            if count == 2 {
+                Err(())
+            } else {
+                Ok((a, b))
+            }
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that collecting drops the collected items on an element error and on a length error.
+    #[test]
+    fn try_collect_array_drop_on_err() {
+        use super::IteratorExt;
+
+        let rc = Rc::new(());
+        let items = [Ok(rc.clone()), Ok(rc.clone()), Err(()), Ok(rc.clone())];
+        assert!(IntoIterator::into_iter(items)
+            .try_collect_array::<_, _, 4>()
+            .is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let items = [Ok::<_, ()>(rc.clone()), Ok(rc.clone())];
+        assert!(IntoIterator::into_iter(items)
+            .try_collect_array::<_, _, 3>()
+            .is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let items = [Ok::<_, ()>(rc.clone()), Ok(rc.clone())];
+        assert!(IntoIterator::into_iter(items)
+            .try_collect_array::<_, _, 1>()
+            .is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while folding drops the accumulator and the elements that weren't visited.
+    #[test]
+    fn try_fold_drop_on_err() {
+        let rc = Rc::new(());
+        let x = [(); 4].map2(|()| rc.clone());
+
+        let res = x.try_fold(std::vec::Vec::new(), |mut acc, elem| {
+            if acc.len() == 2 {
+                return Err(());
+            }
+            acc.push(elem);
+            Ok(acc)
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that reshaping into chunks and flattening them neither leaks nor double drops.
+    #[cfg(feature = "const-exprs")]
+    #[test]
+    fn chunks_round_trip() {
+        use super::{FlattenArrayExt, ReshapeArrayExt};
+
+        let rc = Rc::new(());
+        let x = [(); 6].map2(|()| rc.clone());
+        let chunks = x.into_chunks::<3>();
+        assert_eq!(Rc::strong_count(&rc), 7);
+
+        let y = chunks.flatten();
+        assert_eq!(Rc::strong_count(&rc), 7);
+        assert!(y.iter().all(|elem| Rc::ptr_eq(elem, &rc)));
+
+        mem::drop(y);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while transposing drops the built rows and the remaining elements.
+    #[test]
+    fn try_transpose_drop_on_err() {
+        use super::MatrixExt;
+
+        let rc = Rc::new(());
+        let x = [[(); 3]; 2].map2(|row| row.map2(|()| rc.clone()));
+
+        let mut count = 0;
+        let res = x.try_transpose(|elem| {
+            count += 1;
+            if count == 4 {
+                Err(())
+            } else {
+                Ok(elem)
+            }
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while mapping into a box drops the built elements and the remaining ones.
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_map_boxed_drop_on_err() {
+        let rc = Rc::new(());
+        let x = [(); 4].map2(|()| rc.clone());
+
+        let mut count = 0;
+        let res = super::try_map_boxed(x, |elem| {
+            count += 1;
+            if count == 3 {
+                Err(())
+            } else {
+                Ok(elem)
+            }
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while mapping inside a box drops every element exactly once.
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_map_in_box_drop_on_err() {
+        use super::BoxedArrayExt;
+
+        let rc = Rc::new(());
+        let x = std::boxed::Box::new([(); 4].map2(|()| rc.clone()));
+
+        let mut count = 0;
+        let res = x.try_map_in_box(|elem| {
+            count += 1;
+            if count == 3 {
+                Err(())
+            } else {
+                Ok(Some(elem))
+            }
+        });
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that mapping to a type without drop glue still drops the remaining inputs when `f` panics.
+    #[test]
+    fn trivial_output_drop_on_panic() {
+        let rc = Rc::new(());
+        let x = [(); 4].map2(|()| rc.clone());
+
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let mut count = 0;
+            x.map2(|elem| {
+                count += 1;
+                if count == 3 {
+                    panic!("test panic");
+                }
+                Rc::strong_count(&elem)
+            })
+        }));
+
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that the direct path for elements without drop glue visits every element once, in order.
+    #[test]
+    fn trivial_input_map() {
+        let x: [u32; 1000] = super::from_fn(|i| i as u32);
+
+        let mut visited = 0;
+        let y = x.map2(|v| {
+            assert_eq!(v, visited);
+            visited += 1;
+            v as f32 * 0.5
+        });
+        assert_eq!(visited, 1000);
+        assert_eq!(y[999], 499.5);
+
+        let res = x.try_map(|v| if v < 500 { Ok(v as u8) } else { Err(v) });
+        assert_eq!(res, Err(500));
+    }
+
+    /// A future that is pending the first time it is polled, for the async tests.
+    struct YieldOnce<T>(Option<T>, bool);
+
+    impl<T: Unpin> Future for YieldOnce<T> {
+        type Output = Result<T, ()>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.1 {
+                Poll::Ready(Ok(self.0.take().unwrap()))
+            } else {
+                self.1 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Tests that dropping an unfinished `try_map_async` future drops the built elements and the remaining inputs.
+    #[test]
+    fn try_map_async_cancel() {
+        let rc = Rc::new(());
+        let x = [(); 4].map2(|()| rc.clone());
+
+        let mut fut =
+            std::boxed::Box::pin(super::try_map_async(x, |elem| YieldOnce(Some(elem), false)));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        mem::drop(fut);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that `try_join_map` polls every future before any finishes
+    /// and that dropping it drops the pending futures and the finished outputs.
+    #[test]
+    fn try_join_map_cancel() {
+        let rc = Rc::new(());
+        let x = [(); 4].map2(|()| rc.clone());
+
+        let mut polled = 0;
+        let mut fut = std::boxed::Box::pin(super::try_join_map(x, |elem| {
+            polled += 1;
+            YieldOnce(Some(elem), polled % 2 == 0)
+        }));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        mem::drop(fut);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that an error while mapping a ring buffer drops the mapped elements and the remaining ones.
+    #[test]
+    fn ring_try_map_drop_on_err() {
+        let rc = Rc::new(());
+        let mut ring = super::RingArray::<Rc<()>, 4>::new();
+        for _ in 0..4 {
+            ring.push(rc.clone()).unwrap();
+        }
+        ring.pop();
+        ring.push(rc.clone()).unwrap();
+
+        let mut count = 0;
+        let res = ring.try_map(|elem| {
+            count += 1;
+            if count == 3 {
+                Err(())
+            } else {
+                Ok(elem)
+            }
+        });
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let mut ring = super::RingArray::<Rc<()>, 4>::new();
+        ring.push(rc.clone()).unwrap();
+        ring.push(rc.clone()).unwrap();
+        let mapped = ring.map(Some);
+        assert_eq!(mapped.len(), 2);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        mem::drop(mapped);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that the maps built on `init_with_writer` drop the written elements on errors.
+    #[test]
+    fn writer_maps_drop_on_err() {
+        let rc = Rc::new(());
+
+        let res = [1, 2, 0, 4].try_map_with_prev(rc.clone(), |_, x| {
+            if x == 0 {
+                Err(())
+            } else {
+                Ok(rc.clone())
+            }
+        });
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let mut errors = [const { mem::MaybeUninit::uninit() }; 1];
+        let res = [1, 0, 3].try_map_errors_into(
+            |x| if x == 0 { Err(()) } else { Ok(rc.clone()) },
+            &mut errors,
+        );
+        assert_eq!(res.unwrap_err(), 1);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let (array, state) = [1, 2, 3].map_accum(rc.clone(), |state, _| (state.clone(), state));
+        assert_eq!(Rc::strong_count(&rc), 5);
+        mem::drop((array, state));
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    /// Tests that the successfully mapped prefix of `try_map_partial` can be moved out without cloning,
+    /// and that the elements that aren't moved out are still dropped.
+    #[test]
+    fn try_map_partial_move_out() {
+        #[derive(Debug)]
+        struct NoClone(Rc<()>);
+
+        let rc = Rc::new(());
+        let (err, mut parsed) = [1, 2, 3, 0, 5]
+            .try_map_partial(|x| {
+                if x == 0 {
+                    Err("zero")
+                } else {
+                    Ok(NoClone(rc.clone()))
+                }
+            })
+            .unwrap_err();
+        assert_eq!(err, "zero");
+        assert_eq!(Rc::strong_count(&rc), 4);
+
+        let last = parsed.pop().unwrap();
+        assert!(Rc::ptr_eq(&last.0, &rc));
+        assert_eq!(parsed.len(), 2);
+        mem::drop(last);
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        let mut iter = parsed.into_iter();
+        let first = iter.next().unwrap();
+        assert_eq!(iter.len(), 1);
+        mem::drop(iter);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        mem::drop(first);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 }
@@ -0,0 +1,403 @@
+//! Macros for building arrays.
+
+/// Builds an array from an expression that can use `?`, like an array repeat expression.
+///
+/// `arr_try![expr; N]` evaluates `expr` `N` times, and `arr_try![i => expr; N]`
+/// additionally binds the index of the element being built to `i`.
+/// The macro evaluates to a [`Result`] holding the array, or the first error that `?` returned.
+/// Like with [`try_map`](crate::ArrayMapExt::try_map), the already built elements are dropped when an error occurs.
+///
+/// The error type is inferred from how the result is used,
+/// so it may need an annotation when the result is passed to `?` directly.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::arr_try;
+/// use std::num::ParseIntError;
+///
+/// let input = ["1", "2", "3"];
+/// let x: Result<[u8; 3], ParseIntError> = arr_try![i => input[i].parse::<u8>()? * 2; 3];
+/// assert_eq!(x, Ok([2, 4, 6]));
+///
+/// let input = ["1", "x", "3"];
+/// let x: Result<[u8; 3], ParseIntError> = arr_try![i => input[i].parse()?; 3];
+/// assert!(x.is_err());
+///
+/// let mut lines = "4\n5".lines();
+/// let x: Result<[u32; 2], &str> = arr_try![lines.next().ok_or("missing line")?.len() as u32; 2];
+/// assert_eq!(x, Ok([1, 1]));
+/// ```
+#[macro_export]
+macro_rules! arr_try {
+    ($i:ident => $e:expr; $n:expr) => {
+        $crate::try_init_array::<_, _, _, { $n }>(|$i: usize| ::core::result::Result::Ok($e))
+    };
+    ($e:expr; $n:expr) => {
+        $crate::try_init_array::<_, _, _, { $n }>(|_| ::core::result::Result::Ok($e))
+    };
+}
+
+/// Combines any number of arrays of the same length element by element.
+///
+/// `zip_map!((a, b, c) => |x, y, z| expr)` calls the closure-like body with the elements at each index
+/// of `a`, `b` and `c`, and collects the results into a new array.
+/// The closure must have one parameter per array.
+/// Arrays of different lengths are a compile-time error.
+///
+/// Like with [`map2`](crate::ArrayMapExt::map2), the already built elements and the remaining input elements
+/// are dropped when the body panics.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::zip_map;
+/// let r = [255u8, 0, 10];
+/// let g = [0u8, 255, 20];
+/// let b = [0u8, 0, 30];
+/// let a = [1.0f32, 0.5, 0.0];
+///
+/// let pixels = zip_map!((r, g, b, a) => |r, g, b, a| (u32::from(r) + u32::from(g) + u32::from(b)) as f32 * a);
+/// assert_eq!(pixels, [255.0, 127.5, 0.0]);
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::zip_map;
+/// let x = zip_map!(([1, 2], [3, 4, 5]) => |a, b| a + b);
+/// ```
+#[macro_export]
+macro_rules! zip_map {
+    (@iter $first:ident; $r0:expr $(, $r:expr)*) => {{
+        let array = $r0;
+        $crate::__private::assert_same_len(&$first, &array);
+        let iter = ::core::iter::IntoIterator::into_iter(array);
+        $(
+            let array = $r;
+            $crate::__private::assert_same_len(&$first, &array);
+            let iter = ::core::iter::Iterator::zip(iter, array);
+        )*
+        iter
+    }};
+    (@pat [$acc:pat]) => {
+        $acc
+    };
+    (@pat [$acc:pat] $p:pat_param $(, $ps:pat_param)*) => {
+        $crate::zip_map!(@pat [($acc, $p)] $($ps),*)
+    };
+    (($first:expr $(,)?) => |$p:pat_param $(,)?| $body:expr) => {
+        $crate::ArrayMapExt::map2($first, |$p| $body)
+    };
+    (($first:expr, $($rest:expr),+ $(,)?) => |$p:pat_param, $p0:pat_param $(, $ps:pat_param)* $(,)?| $body:expr) => {{
+        let first = $first;
+        let mut rest = $crate::zip_map!(@iter first; $($rest),+);
+        $crate::ArrayMapExt::map2(first, |$p| {
+            let $crate::zip_map!(@pat [$p0] $($ps),*) = ::core::iter::Iterator::next(&mut rest).unwrap();
+            $body
+        })
+    }};
+}
+
+/// Applies an expression to every element of an array literal, producing an array that can be `const`.
+///
+/// `const_map!([a, b, c] => |x| expr)` expands to `[expr, expr, expr]` with `x` bound to each element in turn,
+/// so as long as `expr` is const-evaluable, the result can initialize a `const` or `static` and is computed at compile time.
+/// This works until a `map` that calls const functions is available.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::const_map;
+/// const fn square(x: u32) -> u32 {
+///     x * x
+/// }
+///
+/// const SQUARES: [u32; 4] = const_map!([1, 2, 3, 4] => |x| square(x));
+/// assert_eq!(SQUARES, [1, 4, 9, 16]);
+///
+/// const MASKS: [u8; 3] = const_map!([0, 3, 7] => |bit| 1 << bit);
+/// assert_eq!(MASKS, [0b1, 0b1000, 0b1000_0000]);
+/// ```
+///
+/// Expressions that can't be evaluated at compile time are rejected in const contexts:
+///
+/// ```compile_fail
+/// # use array_try_map::const_map;
+/// const LENS: [usize; 2] = const_map!(["a", "bb"] => |s| s.to_string().len());
+/// ```
+#[macro_export]
+macro_rules! const_map {
+    ([$($elem:expr),* $(,)?] => |$x:pat_param| $body:expr) => {
+        [$({
+            let $x = $elem;
+            $body
+        }),*]
+    };
+}
+
+/// Builds an array from an expression of a range of indices, where the expression can use `?`.
+///
+/// `seq_array![i in start..end => expr]` evaluates `expr` with `i` bound to every [`usize`] from `start` to `end - 1`.
+/// The range can be followed by modifiers:
+///
+/// - `step s` only uses every `s`th index, starting with `start`.
+/// - `rev` uses the indices in reverse order. It is applied after `step`.
+///
+/// `start`, `end` and `s` must be constants, because they determine the length of the array.
+/// A generic length can be used as `end` if `start` is `0` and there is no `step`.
+/// Like [`arr_try!`](crate::arr_try), the macro evaluates to a [`Result`] holding the array or the first error that `?` returned,
+/// and the error type is inferred from how the result is used.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::seq_array;
+/// use std::convert::{Infallible, TryFrom};
+/// use std::num::TryFromIntError;
+///
+/// let squares: Result<[u8; 16], TryFromIntError> = seq_array![i in 0..16 => u8::try_from(i * i)?];
+/// assert_eq!(squares.unwrap()[15], 225);
+///
+/// let squares: Result<[u8; 17], TryFromIntError> = seq_array![i in 0..17 => u8::try_from(i * i)?];
+/// assert!(squares.is_err());
+///
+/// let evens: Result<[usize; 5], Infallible> = seq_array![i in 0..10, step 2 => i];
+/// assert_eq!(evens, Ok([0, 2, 4, 6, 8]));
+///
+/// let countdown: Result<[usize; 4], Infallible> = seq_array![i in 1..8, step 2, rev => i];
+/// assert_eq!(countdown, Ok([7, 5, 3, 1]));
+///
+/// fn parse_all<const N: usize>(fields: &[&str]) -> Result<[u32; N], std::num::ParseIntError> {
+///     seq_array![i in 0..N, rev => fields[i].parse()?]
+/// }
+/// assert_eq!(parse_all::<3>(&["1", "2", "3"]), Ok([3, 2, 1]));
+/// ```
+#[macro_export]
+macro_rules! seq_array {
+    (@build $i:ident, [$($len:tt)*], $start:tt, $step:tt, $rev:tt, $e:expr) => {{
+        let len: usize = $($len)*;
+        $crate::try_init_array::<_, _, _, { $($len)* }>(|k: usize| {
+            let offset = if $rev { len - 1 - k } else { k };
+            let $i: usize = $start + offset * $step;
+            ::core::result::Result::Ok($e)
+        })
+    }};
+    ($i:ident in $start:tt .. $end:tt, step $step:tt, rev => $e:expr) => {
+        $crate::seq_array!(@build $i, [($end - $start + $step - 1) / $step], $start, $step, true, $e)
+    };
+    ($i:ident in $start:tt .. $end:tt, step $step:tt => $e:expr) => {
+        $crate::seq_array!(@build $i, [($end - $start + $step - 1) / $step], $start, $step, false, $e)
+    };
+    ($i:ident in 0 .. $end:tt, rev => $e:expr) => {
+        $crate::seq_array!(@build $i, [$end], 0, 1, true, $e)
+    };
+    ($i:ident in $start:tt .. $end:tt, rev => $e:expr) => {
+        $crate::seq_array!(@build $i, [$end - $start], $start, 1, true, $e)
+    };
+    ($i:ident in 0 .. $end:tt => $e:expr) => {
+        $crate::seq_array!(@build $i, [$end], 0, 1, false, $e)
+    };
+    ($i:ident in $start:tt .. $end:tt => $e:expr) => {
+        $crate::seq_array!(@build $i, [$end - $start], $start, 1, false, $e)
+    };
+}
+
+/// Defines a `static` array by mapping a const source array at compile time.
+///
+/// `static_map! { static NAME: [U; N] = SOURCE => |x| expr; }` evaluates `expr` for every element of `SOURCE`
+/// during compilation and stores the results in the static, so lookup tables don't need to be built at runtime
+/// or by a build script. The closure-like body can also take the index of the element as `|i, x|`.
+///
+/// `SOURCE` must be a const expression whose elements are [`Copy`], and `expr` must be const-evaluable.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::static_map;
+/// const fn crc32_entry(mut c: u32) -> u32 {
+///     let mut k = 0;
+///     while k < 8 {
+///         c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+///         k += 1;
+///     }
+///     c
+/// }
+///
+/// const INDICES: [u32; 256] = {
+///     let mut indices = [0; 256];
+///     let mut i = 0;
+///     while i < 256 {
+///         indices[i] = i as u32;
+///         i += 1;
+///     }
+///     indices
+/// };
+///
+/// static_map! {
+///     /// The CRC-32 lookup table.
+///     static CRC32_TABLE: [u32; 256] = INDICES => |n| crc32_entry(n);
+/// }
+///
+/// assert_eq!(CRC32_TABLE[1], 0x7707_3096);
+///
+/// static_map! {
+///     pub static WEIGHTED: [u16; 4] = [10u16, 20, 30, 40] => |i, x| x * (i as u16 + 1);
+/// }
+///
+/// assert_eq!(WEIGHTED, [10, 40, 90, 160]);
+/// ```
+#[macro_export]
+macro_rules! static_map {
+    (
+        $(#[$meta:meta])*
+        $vis:vis static $name:ident: [$u:ty; $n:expr] = $src:expr => |$x:pat_param| $body:expr;
+    ) => {
+        $crate::static_map! {
+            $(#[$meta])*
+            $vis static $name: [$u; $n] = $src => |_, $x| $body;
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis static $name:ident: [$u:ty; $n:expr] = $src:expr => |$i:pat_param, $x:pat_param| $body:expr;
+    ) => {
+        $(#[$meta])*
+        $vis static $name: [$u; $n] = {
+            let src = $src;
+            let mut out = [const { ::core::mem::MaybeUninit::<$u>::uninit() }; $n];
+            let mut index = 0;
+            while index < $n {
+                let $i: usize = index;
+                let $x = src[index];
+                out[index] = ::core::mem::MaybeUninit::new($body);
+                index += 1;
+            }
+            // SAFETY: the loop initialized every element.
+            unsafe { ::core::mem::transmute::<[::core::mem::MaybeUninit<$u>; $n], [$u; $n]>(out) }
+        };
+    };
+}
+
+/// Fails to compile unless both arrays have the same length.
+#[inline]
+pub fn assert_same_len<A, B, const N: usize>(_: &[A; N], _: &[B; N]) {}
+
+/// Splits an owned array into the first `A` elements and the remaining `R` elements.
+/// Used by [`split_array!`](crate::split_array).
+#[inline]
+pub fn split_prefix<T, const A: usize, const R: usize, const N: usize>(
+    array: [T; N],
+) -> ([T; A], [T; R]) {
+    let (prefix, rest, []) = crate::ArrayReorderExt::split_ranges::<A, R, 0>(array);
+    (prefix, rest)
+}
+
+/// Destructures an owned array into consecutive arrays, binding each to a variable.
+///
+/// `split_array!(array => [a: 4, b: 2, rest]);` moves the first 4 elements into `a`,
+/// the next 2 into `b` and the remaining ones into `rest`.
+/// The length of the last segment can be omitted, in which case it is inferred from how the variable is used,
+/// for example by passing it to a function that takes an array of a specific length.
+/// All other segments need an explicit length.
+/// Lengths that don't add up to the length of the array are a compile-time error.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::split_array;
+/// fn checksum(payload: [u8; 4]) -> u8 {
+///     payload.iter().fold(0, |a, b| a ^ b)
+/// }
+///
+/// let packet = [0x7E, 0x01, 0x10, 0x20, 0x30, 0x40];
+/// split_array!(packet => [magic: 1, kind: 1, payload]);
+/// assert_eq!(magic, [0x7E]);
+/// assert_eq!(kind, [0x01]);
+/// assert_eq!(checksum(payload), 0x40);
+///
+/// let header = [1u16, 2, 3, 4, 5];
+/// split_array!(header => [version: 1, flags: 2, length: 2]);
+/// assert_eq!(flags, [2, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::split_array;
+/// split_array!([1, 2, 3] => [head: 2, tail: 2]);
+/// ```
+#[macro_export]
+macro_rules! split_array {
+    ($array:expr => [$($segments:tt)+]) => {
+        $crate::split_array!(@collect ($array) []; $($segments)+);
+    };
+    (@collect ($array:expr) [$($acc:tt)*]; $name:ident : $len:expr, $($tail:tt)+) => {
+        $crate::split_array!(@collect ($array) [$($acc)* $name : $len,]; $($tail)+);
+    };
+    (@collect ($array:expr) [$($acc:tt)*]; $name:ident, $($tail:tt)+) => {
+        compile_error!("only the last segment of `split_array!` can omit its length");
+    };
+    (@collect ($array:expr) [$($acc:tt)*]; $name:ident : $len:expr $(,)?) => {
+        $crate::split_array!(@prefix ($array) (_, 0) [$($acc)* $name : $len,]);
+    };
+    (@collect ($array:expr) [$($acc:tt)*]; $name:ident $(,)?) => {
+        $crate::split_array!(@prefix ($array) ($name, _) [$($acc)*]);
+    };
+    (@prefix ($array:expr) ($rest:tt, $rest_len:tt) [$($name:ident : $len:expr,)*]) => {
+        let (prefix, $rest) =
+            $crate::__private::split_prefix::<_, { 0 $(+ $len)* }, $rest_len, _>($array);
+        $crate::split_array!(@peel prefix; $($name : $len),*);
+    };
+    (@peel $prefix:ident; $name:ident : $len:expr, $($tail_name:ident : $tail_len:expr),+) => {
+        let ($name, rest) =
+            $crate::__private::split_prefix::<_, { $len }, { 0 $(+ $tail_len)+ }, _>($prefix);
+        $crate::split_array!(@peel rest; $($tail_name : $tail_len),+);
+    };
+    (@peel $prefix:ident; $name:ident : $len:expr) => {
+        let $name: [_; $len] = $prefix;
+    };
+    (@peel $prefix:ident;) => {
+        let [] = $prefix;
+    };
+}
+
+/// Applies a fallible operation to every field of a tuple with fields of different types.
+///
+/// `tuple_try_map!((a, b, c) => |x| expr)` evaluates the closure-like body once per field,
+/// so the body can call generic functions that produce a different type for every field.
+/// The body must evaluate to a [`Result`], and all fields must fail with the same error type.
+/// The macro evaluates to a [`Result`] holding the tuple of the results, or the first error.
+///
+/// The fields are mapped from left to right.
+/// When an error occurs, the already mapped fields are dropped and the remaining fields are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::tuple_try_map;
+/// use std::num::ParseIntError;
+/// use std::str::FromStr;
+///
+/// fn parse<T: FromStr<Err = ParseIntError>>(s: &str) -> Result<T, ParseIntError> {
+///     s.trim().parse()
+/// }
+///
+/// let parsed: (u8, i64, usize) = tuple_try_map!(("1", " -2", "3 ") => |s| parse(s)).unwrap();
+/// assert_eq!(parsed, (1, -2, 3));
+///
+/// let parsed: Result<(u8, u8), _> = tuple_try_map!(("255", "256") => |s| parse(s));
+/// assert!(parsed.is_err());
+/// ```
+#[macro_export]
+macro_rules! tuple_try_map {
+    (($($field:expr),+ $(,)?) => |$x:pat_param| $body:expr) => {
+        'tuple_try_map: {
+            ::core::result::Result::Ok(($(
+                match {
+                    let $x = $field;
+                    $body
+                } {
+                    ::core::result::Result::Ok(value) => value,
+                    ::core::result::Result::Err(error) => break 'tuple_try_map ::core::result::Result::Err(error),
+                },
+            )+))
+        }
+    };
+}
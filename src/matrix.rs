@@ -0,0 +1,61 @@
+//! Operations on arrays of arrays viewed as matrices.
+
+use crate::{init, ArrayExt};
+
+/// Extension of `[[T; M]; N]`, a matrix of `N` rows and `M` columns.
+pub trait MatrixExt<T, const M: usize, const N: usize> {
+    /// Swaps the rows and columns of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::MatrixExt;
+    /// let x = [[1, 2, 3], [4, 5, 6]];
+    /// assert_eq!(x.transpose(), [[1, 4], [2, 5], [3, 6]]);
+    /// ```
+    fn transpose(self) -> [[T; N]; M];
+
+    /// Swaps the rows and columns of the matrix, mapping every element with a fallible function.
+    ///
+    /// `f` is called on the elements in the order of the transposed matrix, so column by column.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements and the remaining elements of the matrix
+    /// will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements and the remaining elements of the matrix
+    /// will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::MatrixExt;
+    /// let x = [["1", "2"], ["3", "4"]];
+    /// assert_eq!(x.try_transpose(str::parse::<u8>), Ok([[1, 3], [2, 4]]));
+    ///
+    /// let x = [["1", "2"], ["3", "-4"]];
+    /// assert!(x.try_transpose(str::parse::<u8>).is_err());
+    /// ```
+    fn try_transpose<F, U, E>(self, f: F) -> Result<[[U; N]; M], E>
+    where
+        F: FnMut(T) -> Result<U, E>;
+}
+
+impl<T, const M: usize, const N: usize> MatrixExt<T, M, N> for [[T; M]; N] {
+    fn transpose(self) -> [[T; N]; M] {
+        self.try_transpose::<_, _, !>(Ok).into_ok()
+    }
+
+    fn try_transpose<F, U, E>(self, mut f: F) -> Result<[[U; N]; M], E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let mut rows = self.map2(IntoIterator::into_iter);
+        init::try_init_array(|_| init::try_init_array(|row| f(rows[row].next().unwrap())))
+    }
+}
@@ -0,0 +1,82 @@
+//! Removal of one level of nesting from arrays of [`Option`]s and [`Result`]s,
+//! and moving the [`Option`]s and [`Result`]s out of arrays.
+
+use crate::ArrayExt;
+
+/// Flattens every element of an array of nested [`Option`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::flatten_options;
+/// let x = [Some(Some(1)), Some(None), None];
+/// assert_eq!(flatten_options(x), [Some(1), None, None]);
+/// ```
+pub fn flatten_options<T, const N: usize>(array: [Option<Option<T>>; N]) -> [Option<T>; N] {
+    array.map2(Option::flatten)
+}
+
+/// Flattens every element of an array of nested [`Result`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::flatten_results;
+/// let x: [Result<Result<u8, &str>, &str>; 3] = [Ok(Ok(1)), Ok(Err("inner")), Err("outer")];
+/// assert_eq!(flatten_results(x), [Ok(1), Err("inner"), Err("outer")]);
+/// ```
+pub fn flatten_results<T, E, const N: usize>(
+    array: [Result<Result<T, E>, E>; N],
+) -> [Result<T, E>; N] {
+    array.map2(|elem| elem.and_then(|inner| inner))
+}
+
+/// Extension methods for arrays of [`Result`]s.
+pub trait ResultArrayExt<T, E, const N: usize> {
+    /// Turns an array of results into a result of an array, returning the first error if there is one.
+    ///
+    /// # Errors
+    ///
+    /// If any element is an [`Err`], the first such error will be returned by this function.
+    /// The [`Ok`] values will be dropped when an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{ArrayExt, ResultArrayExt};
+    /// let parsed = ["1", "2", "3"].map2(str::parse::<u8>);
+    /// assert_eq!(parsed.transpose_results(), Ok([1, 2, 3]));
+    ///
+    /// let parsed = ["1", "x", "300"].map2(str::parse::<u8>);
+    /// assert_eq!(parsed.transpose_results().unwrap_err().to_string(), "invalid digit found in string");
+    /// ```
+    fn transpose_results(self) -> Result<[T; N], E>;
+}
+
+impl<T, E, const N: usize> ResultArrayExt<T, E, N> for [Result<T, E>; N] {
+    fn transpose_results(self) -> Result<[T; N], E> {
+        self.try_map(|elem| elem)
+    }
+}
+
+/// Extension methods for arrays of [`Option`]s.
+pub trait OptionArrayExt<T, const N: usize> {
+    /// Turns an array of options into an option of an array, returning [`None`] if any element is [`None`].
+    ///
+    /// The [`Some`] values will be dropped if [`None`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::OptionArrayExt;
+    /// assert_eq!([Some(1), Some(2), Some(3)].transpose_options(), Some([1, 2, 3]));
+    /// assert_eq!([Some(1), None, Some(3)].transpose_options(), None);
+    /// ```
+    fn transpose_options(self) -> Option<[T; N]>;
+}
+
+impl<T, const N: usize> OptionArrayExt<T, N> for [Option<T>; N] {
+    fn transpose_options(self) -> Option<[T; N]> {
+        self.opt_map(|elem| elem)
+    }
+}
@@ -0,0 +1,42 @@
+//! Zero-cost conversions between arrays of newtypes and arrays of their inner type.
+
+/// A newtype that has the same layout as `Inner`.
+///
+/// Implementing this allows [`wrap`](crate::ArrayConvertExt::wrap) and [`peel`](crate::ArrayConvertExt::peel)
+/// to convert whole arrays without touching the elements.
+/// Newtypes that aren't `#[repr(transparent)]` can still be converted element by element
+/// with [`map_into`](crate::ArrayConvertExt::map_into) by implementing [`From`] instead.
+///
+/// # Safety
+///
+/// `Self` must be `#[repr(transparent)]` and `Inner` must be the type of its only non-zero-sized field.
+/// Wrapping must not break any invariant of `Self`, since any `Inner` can be wrapped.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{ArrayExt, TransparentWrapper};
+/// #[derive(Debug, PartialEq)]
+/// #[repr(transparent)]
+/// struct Meters(f32);
+///
+/// unsafe impl TransparentWrapper<f32> for Meters {}
+///
+/// let x = [1.0, 2.5];
+/// let y: [Meters; 2] = x.wrap();
+/// assert_eq!(y, [Meters(1.0), Meters(2.5)]);
+/// assert_eq!(y.peel(), [1.0, 2.5]);
+/// ```
+pub unsafe trait TransparentWrapper<Inner>: Sized {}
+
+/// Reinterprets an array as an array of another type with the same layout.
+///
+/// # Safety
+///
+/// `T` and `U` must have the same layout and every `T` must be a valid `U`.
+pub(crate) unsafe fn reinterpret<T, U, const N: usize>(array: [T; N]) -> [U; N] {
+    let array = core::mem::ManuallyDrop::new(array);
+    // SAFETY: the caller guarantees that `[T; N]` and `[U; N]` have the same layout,
+    // and `array` is never dropped so its elements are moved rather than duplicated.
+    unsafe { core::ptr::read(&*array as *const [T; N] as *const [U; N]) }
+}
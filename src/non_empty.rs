@@ -0,0 +1,168 @@
+//! Methods on arrays that are statically known not to be empty.
+
+use core::cmp::Ordering;
+
+use crate::assert::AssertNonZero;
+use crate::AsArray;
+
+/// Extension of `[T; N]` with methods that don't need to handle the empty case because `N` is at least 1.
+///
+/// Calling any of these methods on an empty array is a compile-time error.
+///
+/// ```compile_fail
+/// # use array_try_map::NonEmptyArrayExt;
+/// let x: [u8; 0] = [];
+/// let _ = x.first();
+/// ```
+pub trait NonEmptyArrayExt<T, const N: usize> {
+    /// Returns the first element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [1, 2, 3];
+    /// assert_eq!(*x.first(), 1);
+    /// ```
+    fn first(&self) -> &T;
+
+    /// Returns the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [1, 2, 3];
+    /// assert_eq!(*x.last(), 3);
+    /// ```
+    fn last(&self) -> &T;
+
+    /// Combines all elements into one by repeatedly applying `f`, starting with the first element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The remaining elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [1, 2, 3, 4];
+    /// assert_eq!(x.reduce(|a, b| a * b), 24);
+    /// ```
+    fn reduce<F>(self, f: F) -> T
+    where
+        F: FnMut(T, T) -> T;
+
+    /// Fallible version of [`reduce`](NonEmptyArrayExt::reduce).
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The remaining elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The remaining elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [2u8, 3, 4];
+    /// assert_eq!(x.try_reduce(|a, b| a.checked_mul(b).ok_or("overflow")), Ok(24));
+    ///
+    /// let x = [20u8, 30, 40];
+    /// assert_eq!(x.try_reduce(|a, b| a.checked_mul(b).ok_or("overflow")), Err("overflow"));
+    /// ```
+    fn try_reduce<F, E>(self, f: F) -> Result<T, E>
+    where
+        F: FnMut(T, T) -> Result<T, E>;
+
+    /// Returns the minimum element according to `compare`.
+    /// If several elements are equally minimum, the first one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [-3i32, 1, 3];
+    /// assert_eq!(*x.min_by(|a, b| a.abs().cmp(&b.abs())), 1);
+    /// ```
+    fn min_by<F>(&self, compare: F) -> &T
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Returns the maximum element according to `compare`.
+    /// If several elements are equally maximum, the last one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::NonEmptyArrayExt;
+    /// let x = [-3i32, 1, 3];
+    /// assert_eq!(*x.max_by(|a, b| a.abs().cmp(&b.abs())), 3);
+    /// ```
+    fn max_by<F>(&self, compare: F) -> &T
+    where
+        F: FnMut(&T, &T) -> Ordering;
+}
+
+impl<Arr, T, const N: usize> NonEmptyArrayExt<T, N> for Arr
+where
+    Arr: AsArray<T, N>,
+{
+    fn first(&self) -> &T {
+        let array = AsArray::as_array(self);
+        let () = AssertNonZero::<N>::OK;
+        &array[0]
+    }
+
+    fn last(&self) -> &T {
+        let array = AsArray::as_array(self);
+        let () = AssertNonZero::<N>::OK;
+        &array[N - 1]
+    }
+
+    fn reduce<F>(self, f: F) -> T
+    where
+        F: FnMut(T, T) -> T,
+    {
+        let array = AsArray::into_array(self);
+        let () = AssertNonZero::<N>::OK;
+        let mut iter = IntoIterator::into_iter(array);
+        let first = iter.next().unwrap();
+        iter.fold(first, f)
+    }
+
+    fn try_reduce<F, E>(self, f: F) -> Result<T, E>
+    where
+        F: FnMut(T, T) -> Result<T, E>,
+    {
+        let array = AsArray::into_array(self);
+        let () = AssertNonZero::<N>::OK;
+        let mut iter = IntoIterator::into_iter(array);
+        let first = iter.next().unwrap();
+        iter.try_fold(first, f)
+    }
+
+    fn min_by<F>(&self, mut compare: F) -> &T
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let array = AsArray::as_array(self);
+        let () = AssertNonZero::<N>::OK;
+        array.iter().min_by(|a, b| compare(a, b)).unwrap()
+    }
+
+    fn max_by<F>(&self, mut compare: F) -> &T
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let array = AsArray::as_array(self);
+        let () = AssertNonZero::<N>::OK;
+        array.iter().max_by(|a, b| compare(a, b)).unwrap()
+    }
+}
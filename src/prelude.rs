@@ -0,0 +1,38 @@
+//! The extension traits of this crate, for glob importing.
+//!
+//! Importing the prelude brings the extension traits into scope at once:
+//!
+//! ```
+//! use array_try_map::prelude::*;
+//!
+//! let x = ["1", "2", "3"].try_map(str::parse::<u8>).unwrap();
+//! assert_eq!(x.zip_with([4, 5, 6], |a, b| a + b), [5, 7, 9]);
+//! assert_eq!([[1, 2], [3, 4]].transpose(), [[1, 3], [2, 4]]);
+//! assert_eq!("4 5".split(' ').collect_array(), Ok(["4", "5"]));
+//! ```
+//!
+//! [`NonEmptyArrayExt`](crate::NonEmptyArrayExt) and `ReshapeArrayExt` aren't part of the prelude,
+//! because their `first`, `last`, `concat` and `split` methods would take priority over the slice methods
+//! of the same names. They have to be imported explicitly:
+//!
+//! ```
+//! use array_try_map::prelude::*;
+//!
+//! let x = [1, 2, 3];
+//! if let Some(first) = x.first() {
+//!     assert_eq!(*first, 1);
+//! }
+//! assert_eq!(["a", "b"].concat(), "ab");
+//! ```
+//!
+//! To keep the methods available without their names, the traits can also be imported one by one
+//! with `use array_try_map::ArrayExt as _;`.
+
+#[cfg(feature = "alloc")]
+pub use crate::BoxedArrayExt;
+#[cfg(feature = "const-exprs")]
+pub use crate::FlattenArrayExt;
+pub use crate::{
+    ArrayConvertExt, ArrayExt, ArrayFoldExt, ArrayMapExt, ArrayReorderExt, ArrayWindowExt,
+    ArrayZipExt, IteratorExt, MatrixExt, OptionArrayExt, ResultArrayExt, UnzipArrayExt,
+};
@@ -0,0 +1,40 @@
+//! Shuffling and sampling with a caller-provided source of randomness.
+
+/// A source of uniformly distributed random indices,
+/// used by [`shuffled`](crate::ArrayReorderExt::shuffled) and [`sample`](crate::ArrayReorderExt::sample).
+///
+/// It is implemented for closures taking the length of the range and returning an index in it,
+/// so any random number generator can be plugged in.
+/// With the `rand` crate, this looks like `&mut |len| rng.gen_range(0..len)`.
+pub trait RandomIndex {
+    /// Returns a random index less than `len`, which is never zero.
+    fn random_index(&mut self, len: usize) -> usize;
+}
+
+impl<F> RandomIndex for F
+where
+    F: FnMut(usize) -> usize,
+{
+    #[inline]
+    fn random_index(&mut self, len: usize) -> usize {
+        self(len)
+    }
+}
+
+/// Moves a uniformly random selection of `count` elements to the front of the slice, in random order.
+pub(crate) fn partial_shuffle<T, R>(slice: &mut [T], count: usize, rng: &mut R)
+where
+    R: RandomIndex + ?Sized,
+{
+    let len = slice.len();
+    for i in 0..count.min(len.saturating_sub(1)) {
+        let offset = rng.random_index(len - i);
+        assert!(
+            offset < len - i,
+            "`random_index` returned {} for a length of {}",
+            offset,
+            len - i
+        );
+        slice.swap(i, i + offset);
+    }
+}
@@ -0,0 +1,214 @@
+//! Methods on arrays whose output lengths are computed from the input lengths.
+
+use core::mem::ManuallyDrop;
+
+use crate::assert::{AssertDivisible, AssertLe};
+use crate::ArrayExt;
+
+/// Extension of `[T; N]` with methods that return arrays of a length computed from `N`.
+///
+/// These methods need the `const-exprs` feature. Calling them with lengths that aren't known
+/// may need `where [(); N + M]:` bounds for every computed length.
+pub trait ReshapeArrayExt<T, const N: usize> {
+    /// Moves the elements of this array and then those of `other` into one array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let header = [0xCA, 0xFE];
+    /// let body = [1, 2, 3];
+    /// assert_eq!(header.concat(body), [0xCA, 0xFE, 1, 2, 3]);
+    /// ```
+    fn concat<const M: usize>(self, other: [T; M]) -> [T; N + M];
+
+    /// Moves the first `M` elements into one array and the rest into another.
+    ///
+    /// This is the inverse of [`concat`](ReshapeArrayExt::concat).
+    /// `M` must not be greater than `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let packet = [0xCA, 0xFE, 1, 2, 3];
+    /// let (header, payload) = packet.split::<2>();
+    /// assert_eq!(header, [0xCA, 0xFE]);
+    /// assert_eq!(payload, [1, 2, 3]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let (a, b) = [1, 2, 3].split::<4>();
+    /// ```
+    fn split<const M: usize>(self) -> ([T; M], [T; N - M]);
+
+    /// Reshapes the array into an array of chunks of `M` consecutive elements.
+    ///
+    /// `M` must divide `N`. The elements aren't moved individually,
+    /// since an array of `N / M` arrays of length `M` has the same layout as an array of length `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let rgba = [255, 0, 0, 255, 0, 255, 0, 128];
+    /// assert_eq!(rgba.into_chunks::<4>(), [[255, 0, 0, 255], [0, 255, 0, 128]]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let chunks = [1, 2, 3].into_chunks::<2>();
+    /// ```
+    fn into_chunks<const M: usize>(self) -> [[T; M]; N / M];
+
+    /// Maps every chunk of `M` consecutive elements to one element of a new array.
+    ///
+    /// `M` must divide `N`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let bytes = [0, 0, 0, 1, 0, 0, 1, 0];
+    /// assert_eq!(bytes.map_chunks(u32::from_be_bytes), [1, 256]);
+    /// ```
+    fn map_chunks<const M: usize, F, U>(self, f: F) -> [U; N / M]
+    where
+        F: FnMut([T; M]) -> U;
+
+    /// Fallible version of [`map_chunks`](ReshapeArrayExt::map_chunks).
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// let utf16 = [0x0048, 0x0069, 0xD83D, 0xDE00];
+    /// let pairs = utf16.try_map_chunks(|[a, b]| {
+    ///     char::decode_utf16([a, b]).collect::<Result<String, _>>()
+    /// });
+    /// assert_eq!(pairs.unwrap(), ["Hi", "\u{1F600}"]);
+    /// ```
+    fn try_map_chunks<const M: usize, F, U, E>(self, f: F) -> Result<[U; N / M], E>
+    where
+        F: FnMut([T; M]) -> Result<U, E>;
+
+    /// Maps every element to an array of `M` elements and joins the results into one array.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::ReshapeArrayExt;
+    /// use std::convert::TryFrom;
+    ///
+    /// let samples = [1i32, -2, 300];
+    /// let encoded = samples.try_flat_map(|s| i16::try_from(s).map(i16::to_le_bytes));
+    /// assert_eq!(encoded, Ok([1, 0, 0xFE, 0xFF, 0x2C, 0x01]));
+    ///
+    /// let encoded = [1i32, 70000].try_flat_map(|s| i16::try_from(s).map(i16::to_le_bytes));
+    /// assert!(encoded.is_err());
+    /// ```
+    fn try_flat_map<const M: usize, F, U, E>(self, f: F) -> Result<[U; N * M], E>
+    where
+        F: FnMut(T) -> Result<[U; M], E>;
+}
+
+impl<T, const N: usize> ReshapeArrayExt<T, N> for [T; N] {
+    fn concat<const M: usize>(self, other: [T; M]) -> [T; N + M] {
+        let mut iter = IntoIterator::into_iter(self).chain(other);
+        [(); N + M].map2(|()| iter.next().unwrap())
+    }
+
+    fn split<const M: usize>(self) -> ([T; M], [T; N - M]) {
+        let () = AssertLe::<M, N>::OK;
+        let mut iter = IntoIterator::into_iter(self);
+        let a = [(); M].map2(|()| iter.next().unwrap());
+        let b = [(); N - M].map2(|()| iter.next().unwrap());
+        (a, b)
+    }
+
+    fn into_chunks<const M: usize>(self) -> [[T; M]; N / M] {
+        let () = AssertDivisible::<N, M>::OK;
+        let array = ManuallyDrop::new(self);
+        // SAFETY: `M` divides `N`, so `[[T; M]; N / M]` consists of exactly the `N` elements of `[T; N]`
+        // laid out in the same order, and `array` won't drop them.
+        unsafe { core::mem::transmute_copy::<ManuallyDrop<[T; N]>, [[T; M]; N / M]>(&array) }
+    }
+
+    fn map_chunks<const M: usize, F, U>(self, f: F) -> [U; N / M]
+    where
+        F: FnMut([T; M]) -> U,
+    {
+        self.into_chunks::<M>().map2(f)
+    }
+
+    fn try_map_chunks<const M: usize, F, U, E>(self, f: F) -> Result<[U; N / M], E>
+    where
+        F: FnMut([T; M]) -> Result<U, E>,
+    {
+        self.into_chunks::<M>().try_map(f)
+    }
+
+    fn try_flat_map<const M: usize, F, U, E>(self, f: F) -> Result<[U; N * M], E>
+    where
+        F: FnMut(T) -> Result<[U; M], E>,
+    {
+        self.try_map(f).map(FlattenArrayExt::flatten)
+    }
+}
+
+/// Extension of `[[T; M]; N]` with a method that removes the inner level of arrays.
+///
+/// This needs the `const-exprs` feature.
+pub trait FlattenArrayExt<T, const M: usize, const N: usize> {
+    /// Joins the inner arrays into one array of `N * M` elements.
+    ///
+    /// This is the inverse of [`into_chunks`](ReshapeArrayExt::into_chunks).
+    /// The elements aren't moved individually,
+    /// since an array of `N` arrays of length `M` has the same layout as an array of length `N * M`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{FlattenArrayExt, ReshapeArrayExt};
+    /// let pixels = [[255, 0, 0, 255], [0, 255, 0, 128]];
+    /// let rgba = pixels.flatten();
+    /// assert_eq!(rgba, [255, 0, 0, 255, 0, 255, 0, 128]);
+    /// assert_eq!(rgba.into_chunks::<4>(), pixels);
+    /// ```
+    fn flatten(self) -> [T; N * M];
+}
+
+impl<T, const M: usize, const N: usize> FlattenArrayExt<T, M, N> for [[T; M]; N] {
+    fn flatten(self) -> [T; N * M] {
+        let array = ManuallyDrop::new(self);
+        // SAFETY: `[T; N * M]` consists of exactly the `N * M` elements of `[[T; M]; N]`
+        // laid out in the same order, and `array` won't drop them.
+        unsafe { core::mem::transmute_copy::<ManuallyDrop<[[T; M]; N]>, [T; N * M]>(&array) }
+    }
+}
@@ -0,0 +1,189 @@
+//! A fixed-capacity ring buffer.
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use crate::PartialInit;
+
+/// A first-in, first-out queue of up to `N` elements stored inline in an array.
+///
+/// Elements are added at the back with [`push`](RingArray::push) and removed from the front
+/// with [`pop`](RingArray::pop), wrapping around the end of the array.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::RingArray;
+/// let mut recent = RingArray::<u32, 3>::new();
+/// for sample in [10, 20, 30] {
+///     recent.push(sample).unwrap();
+/// }
+/// assert_eq!(recent.push(40), Err(40));
+///
+/// assert_eq!(recent.pop(), Some(10));
+/// recent.push(40).unwrap();
+///
+/// let scaled = recent.try_map(|x| x.checked_mul(2).ok_or("overflow")).unwrap();
+/// assert!(scaled.iter().eq(&[40, 60, 80]));
+/// ```
+pub struct RingArray<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> RingArray<T, N> {
+    /// Creates an empty ring buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: [const { MaybeUninit::uninit() }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if there are `N` elements.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the maximum number of elements, `N`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the index into `buf` of the `i`th element from the front.
+    #[inline]
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % N
+    }
+
+    /// Adds an element at the back.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is full, the element is given back.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        let slot = self.slot(self.len);
+        self.buf[slot].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the element at the front, if there is one.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: the buffer isn't empty, so the slot at `head` is initialized,
+        // and it is no longer considered initialized after this.
+        let value = unsafe { self.buf[self.head].assume_init_read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Borrows the element at the front, if there is one.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Borrows the `index`th element from the front, if there is one.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            // SAFETY: the first `len` slots from `head` are initialized.
+            Some(unsafe { self.buf[self.slot(index)].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements from front to back.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).map(move |i| {
+            // SAFETY: the first `len` slots from `head` are initialized.
+            unsafe { self.buf[self.slot(i)].assume_init_ref() }
+        })
+    }
+
+    /// Fallible version of [`map`](RingArray::map).
+    /// The provided function will be run on every element from front to back
+    /// until the buffer ends or an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already mapped elements and the remaining elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the remaining elements will be dropped when a panic occurs.
+    pub fn try_map<F, U, E>(mut self, mut f: F) -> Result<RingArray<U, N>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let mut buf = [const { MaybeUninit::uninit() }; N];
+        let mut dst = PartialInit::new(&mut buf);
+        // `self` holds at most `N` elements, so `dst` never runs out of space.
+        while let Some(src) = self.pop() {
+            dst.write_next(f(src)?);
+        }
+        let len = dst.finish_prefix_in_place().len();
+        Ok(RingArray { buf, head: 0, len })
+    }
+
+    /// Maps every element from front to back into a new ring buffer with the same capacity.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already mapped elements and the remaining elements will be dropped when a panic occurs.
+    pub fn map<F, U>(self, mut f: F) -> RingArray<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        self.try_map::<_, _, !>(|src| Ok(f(src))).into_ok()
+    }
+}
+
+impl<T, const N: usize> Default for RingArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for RingArray<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for RingArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,51 @@
+//! Run-length encoding of arrays.
+
+use crate::error::LenError;
+use crate::init::init_with_writer;
+
+/// Expands runs of repeated elements, as produced by [`run_length_encode`](crate::ArrayFoldExt::run_length_encode),
+/// back into an array.
+///
+/// Runs with a count of zero are skipped.
+///
+/// # Errors
+///
+/// If the counts don't add up to `N`, a [`LenError`] with their sum will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{run_length_decode, ArrayExt};
+/// let pixels = [0u8, 0, 0, 255, 255, 0];
+/// let runs = pixels.run_length_encode();
+/// assert_eq!(runs, [(0, 3), (255, 2), (0, 1)]);
+/// assert_eq!(run_length_decode::<_, 6>(&runs), Ok(pixels));
+///
+/// let err = run_length_decode::<u8, 4>(&[(1, 2), (2, 3)]).unwrap_err();
+/// assert_eq!((err.expected, err.actual), (4, 5));
+/// ```
+pub fn run_length_decode<T, const N: usize>(runs: &[(T, usize)]) -> Result<[T; N], LenError>
+where
+    T: Clone,
+{
+    let actual = runs
+        .iter()
+        .fold(0usize, |sum, (_, count)| sum.saturating_add(*count));
+    if actual != N {
+        return Err(LenError {
+            expected: N,
+            actual,
+        });
+    }
+
+    // The counts add up to `N`, so the array is filled exactly.
+    Ok(init_with_writer::<_, !, _, N>(|dst| {
+        for (value, count) in runs {
+            for _ in 0..*count {
+                dst.write_next(value.clone());
+            }
+        }
+        Ok(())
+    })
+    .into_ok())
+}
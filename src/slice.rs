@@ -0,0 +1,75 @@
+//! Conversions from slices into arrays with descriptive errors.
+
+use core::convert::TryInto;
+
+use crate::LenError;
+
+fn len_error<T, const N: usize>(slice: &[T]) -> LenError {
+    LenError {
+        expected: N,
+        actual: slice.len(),
+    }
+}
+
+/// Converts a slice with exactly `N` elements into a reference to an array.
+///
+/// # Errors
+///
+/// If the slice doesn't have exactly `N` elements, a [`LenError`] with its length will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::as_array;
+/// let x = [1, 2, 3, 4];
+/// assert_eq!(as_array::<_, 2>(&x[1..3]), Ok(&[2, 3]));
+///
+/// let err = as_array::<_, 2>(&x[..]).unwrap_err();
+/// assert_eq!(err.to_string(), "expected a length of 2, found 4");
+/// ```
+pub fn as_array<T, const N: usize>(slice: &[T]) -> Result<&[T; N], LenError> {
+    slice.try_into().map_err(|_| len_error::<T, N>(slice))
+}
+
+/// Converts a mutable slice with exactly `N` elements into a mutable reference to an array.
+///
+/// # Errors
+///
+/// If the slice doesn't have exactly `N` elements, a [`LenError`] with its length will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::as_mut_array;
+/// let mut x = [1, 2, 3, 4];
+/// let middle: &mut [i32; 2] = as_mut_array(&mut x[1..3]).unwrap();
+/// middle.swap(0, 1);
+/// assert_eq!(x, [1, 3, 2, 4]);
+/// ```
+pub fn as_mut_array<T, const N: usize>(slice: &mut [T]) -> Result<&mut [T; N], LenError> {
+    let err = len_error::<T, N>(slice);
+    slice.try_into().map_err(|_| err)
+}
+
+/// Clones a slice with exactly `N` elements into an array.
+///
+/// # Errors
+///
+/// If the slice doesn't have exactly `N` elements, a [`LenError`] with its length will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::to_array;
+/// let x = vec![String::from("a"), String::from("b")];
+/// let y: [String; 2] = to_array(&x).unwrap();
+/// assert_eq!(y, ["a", "b"]);
+///
+/// assert_eq!(to_array::<_, 3>(&x).unwrap_err().actual, 2);
+/// ```
+pub fn to_array<T, const N: usize>(slice: &[T]) -> Result<[T; N], LenError>
+where
+    T: Clone,
+{
+    as_array(slice).cloned()
+}
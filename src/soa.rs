@@ -0,0 +1,176 @@
+//! Conversions between arrays of structs and structs of arrays.
+
+use core::mem::MaybeUninit;
+
+use crate::init::PartialInit;
+use crate::ArrayExt;
+
+/// A struct that can be split into one array per field.
+///
+/// The struct of arrays is usually generated together with this implementation by [`impl_soa!`](crate::impl_soa).
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{impl_soa, ArrayExt, StructOfArrays};
+/// #[derive(Debug, PartialEq)]
+/// struct Particle {
+///     pos: f32,
+///     vel: f32,
+/// }
+///
+/// impl_soa! {
+///     Particle => pub struct Particles {
+///         pub pos: f32,
+///         pub vel: f32,
+///     }
+/// }
+///
+/// let x = [Particle { pos: 0.0, vel: 1.0 }, Particle { pos: 2.0, vel: -1.0 }];
+/// let mut soa = x.into_soa();
+/// assert_eq!(soa.pos, [0.0, 2.0]);
+///
+/// soa.pos = soa.pos.map2(|p| p + 1.0);
+/// let y = Particle::from_soa(soa);
+/// assert_eq!(y, [Particle { pos: 1.0, vel: 1.0 }, Particle { pos: 3.0, vel: -1.0 }]);
+/// ```
+pub trait StructOfArrays<const N: usize>: Sized {
+    /// The struct with one array of length `N` per field.
+    type Arrays;
+
+    /// Moves every field of every element into the array for that field.
+    fn into_soa(array: [Self; N]) -> Self::Arrays;
+
+    /// Moves the elements of every field array back into an array of structs.
+    fn from_soa(arrays: Self::Arrays) -> [Self; N];
+}
+
+/// Pairs are split into a tuple of two arrays.
+impl<A, B, const N: usize> StructOfArrays<N> for (A, B) {
+    type Arrays = ([A; N], [B; N]);
+
+    fn into_soa(array: [Self; N]) -> Self::Arrays {
+        let (mut a, mut b) = (
+            MaybeUninit::<[A; N]>::uninit(),
+            MaybeUninit::<[B; N]>::uninit(),
+        );
+        // SAFETY: no element is initialized yet.
+        let (mut a_dst, mut b_dst) = unsafe {
+            (
+                PartialInit::resume(&mut a, 0),
+                PartialInit::resume(&mut b, 0),
+            )
+        };
+        for (x, y) in IntoIterator::into_iter(array) {
+            a_dst.write_next(x);
+            b_dst.write_next(y);
+        }
+        a_dst.finish_in_place();
+        b_dst.finish_in_place();
+        // SAFETY: every element of both arrays has been initialized, and the writers no longer drop them.
+        unsafe { (a.assume_init(), b.assume_init()) }
+    }
+
+    fn from_soa((a, b): Self::Arrays) -> [Self; N] {
+        let mut b = IntoIterator::into_iter(b);
+        a.map2(|x| (x, b.next().unwrap()))
+    }
+}
+
+/// Extension methods for arrays of pairs.
+pub trait UnzipArrayExt<A, B, const N: usize> {
+    /// Splits an array of pairs into an array of the first elements and an array of the second elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::UnzipArrayExt;
+    /// let (keys, values) = [("a", 1), ("b", 2), ("c", 3)].unzip();
+    /// assert_eq!(keys, ["a", "b", "c"]);
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    fn unzip(self) -> ([A; N], [B; N]);
+}
+
+impl<A, B, const N: usize> UnzipArrayExt<A, B, N> for [(A, B); N] {
+    fn unzip(self) -> ([A; N], [B; N]) {
+        StructOfArrays::into_soa(self)
+    }
+}
+
+/// Defines a struct of arrays for a struct and implements [`StructOfArrays`] for it.
+///
+/// The generated struct takes the array length as a const generic parameter
+/// and has one array field per listed field of the original struct.
+/// Every field of the original struct must be listed, and it must not implement [`Drop`].
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{impl_soa, ArrayExt};
+/// struct Rgb {
+///     r: u8,
+///     g: u8,
+///     b: u8,
+/// }
+///
+/// impl_soa! {
+///     Rgb => #[derive(Debug)] pub struct RgbPlanes {
+///         pub r: u8,
+///         pub g: u8,
+///         pub b: u8,
+///     }
+/// }
+///
+/// let planes: RgbPlanes<2> = [Rgb { r: 1, g: 2, b: 3 }, Rgb { r: 4, g: 5, b: 6 }].into_soa();
+/// assert_eq!(planes.g, [2, 5]);
+/// ```
+#[macro_export]
+macro_rules! impl_soa {
+    (
+        $ty:ident => $(#[$meta:meta])* $vis:vis struct $name:ident {
+            $($field_vis:vis $field:ident: $field_ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name<const N: usize> {
+            $($field_vis $field: [$field_ty; N],)+
+        }
+
+        impl<const N: usize> $crate::StructOfArrays<N> for $ty {
+            type Arrays = $name<N>;
+
+            fn into_soa(array: [Self; N]) -> Self::Arrays {
+                struct Buffers<const N: usize> {
+                    $($field: [::core::mem::MaybeUninit<$field_ty>; N],)+
+                }
+
+                struct Writers<'a, const N: usize> {
+                    $($field: $crate::PartialInit<'a, $field_ty, N>,)+
+                }
+
+                let mut buffers = Buffers::<N> {
+                    $($field: [const { ::core::mem::MaybeUninit::uninit() }; N],)+
+                };
+                let mut writers = Writers::<N> {
+                    $($field: $crate::PartialInit::new(&mut buffers.$field),)+
+                };
+                for $ty { $($field),+ } in ::core::iter::IntoIterator::into_iter(array) {
+                    $(writers.$field.write_next($field);)+
+                }
+                $name {
+                    $($field: writers.$field.finish(),)+
+                }
+            }
+
+            fn from_soa(arrays: Self::Arrays) -> [Self; N] {
+                $(
+                    let mut $field = ::core::iter::IntoIterator::into_iter(arrays.$field);
+                )+
+                $crate::ArrayMapExt::map2([(); N], |()| $ty {
+                    $($field: $field.next().unwrap(),)+
+                })
+            }
+        }
+    };
+}
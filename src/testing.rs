@@ -0,0 +1,72 @@
+//! Assertions for tests involving arrays.
+
+/// Arrays up to this length are printed in full when [`assert_array_eq!`](crate::assert_array_eq) fails.
+pub const FULL_DIFF_MAX_LEN: usize = 16;
+
+/// Returns the index of the first pair of elements that differ, and the number of such pairs.
+pub fn differences<T, U, const N: usize>(left: &[T; N], right: &[U; N]) -> Option<(usize, usize)>
+where
+    T: PartialEq<U>,
+{
+    let mut differing = left
+        .iter()
+        .zip(right)
+        .enumerate()
+        .filter(|(_, (l, r))| *l != *r);
+    let (first, _) = differing.next()?;
+    Some((first, 1 + differing.count()))
+}
+
+/// Asserts that two arrays are equal, reporting the first index where they differ.
+///
+/// Unlike [`assert_eq!`], the panic message points directly at the first differing element
+/// and says how many elements differ, instead of only printing both arrays.
+/// Arrays of up to 16 elements are printed in full as well.
+/// Arrays of different lengths are a compile-time error.
+///
+/// Like [`assert_eq!`], a custom message can be passed after the arrays.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::assert_array_eq;
+/// let x = [1, 2, 3];
+/// assert_array_eq!(x, [1, 2, 3]);
+/// ```
+///
+/// ```should_panic
+/// # use array_try_map::assert_array_eq;
+/// let mut x = [0u8; 1000];
+/// x[500] = 1;
+/// assert_array_eq!(x, [0u8; 1000], "buffer should be cleared");
+/// ```
+#[macro_export]
+macro_rules! assert_array_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_array_eq!(@check $left, $right, ::core::format_args!(""))
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::assert_array_eq!(@check $left, $right, ::core::format_args!(": {}", ::core::format_args!($($arg)+)))
+    };
+    (@check $left:expr, $right:expr, $msg:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let ::core::option::Option::Some((index, count)) =
+                    $crate::__private::differences(left, right)
+                {
+                    if left.len() <= $crate::__private::FULL_DIFF_MAX_LEN {
+                        ::core::panic!(
+                            "assertion `left == right` failed{}\n{} of {} elements differ, first at index {}\n  left[{}]: {:?}\n right[{}]: {:?}\n  left: {:?}\n right: {:?}",
+                            $msg, count, left.len(), index, index, left[index], index, right[index], left, right,
+                        );
+                    } else {
+                        ::core::panic!(
+                            "assertion `left == right` failed{}\n{} of {} elements differ, first at index {}\n  left[{}]: {:?}\n right[{}]: {:?}",
+                            $msg, count, left.len(), index, index, left[index], index, right[index],
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,61 @@
+//! Conversions from strings into fixed-width arrays.
+
+use core::convert::TryInto;
+
+use crate::{ArrayExt, AsciiError, LenError};
+
+/// Converts a string with exactly `N` characters into an array of [`char`]s.
+///
+/// # Errors
+///
+/// If the string doesn't have exactly `N` characters, a [`LenError`] with its number of characters will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::str_to_chars;
+/// assert_eq!(str_to_chars("añb"), Ok(['a', 'ñ', 'b']));
+///
+/// let err = str_to_chars::<4>("añb").unwrap_err();
+/// assert_eq!(err.actual, 3);
+/// ```
+pub fn str_to_chars<const N: usize>(s: &str) -> Result<[char; N], LenError> {
+    let actual = s.chars().count();
+    if actual != N {
+        return Err(LenError {
+            expected: N,
+            actual,
+        });
+    }
+    let mut chars = s.chars();
+    Ok([(); N].map2(|()| chars.next().unwrap()))
+}
+
+/// Converts an ASCII string with exactly `N` bytes into an array of bytes.
+///
+/// # Errors
+///
+/// If the string contains a non-ASCII character, [`AsciiError::NonAscii`] with its position will be returned.
+/// Otherwise, if the string doesn't have exactly `N` bytes, [`AsciiError::Len`] will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{str_to_ascii, AsciiError};
+/// assert_eq!(str_to_ascii("GET"), Ok(*b"GET"));
+///
+/// assert_eq!(str_to_ascii::<3>("añb"), Err(AsciiError::NonAscii { index: 1 }));
+/// assert!(matches!(str_to_ascii::<4>("GET"), Err(AsciiError::Len(_))));
+/// ```
+pub fn str_to_ascii<const N: usize>(s: &str) -> Result<[u8; N], AsciiError> {
+    let bytes = s.as_bytes();
+    if let Some(index) = bytes.iter().position(|b| !b.is_ascii()) {
+        return Err(AsciiError::NonAscii { index });
+    }
+    bytes.try_into().map_err(|_| {
+        AsciiError::Len(LenError {
+            expected: N,
+            actual: bytes.len(),
+        })
+    })
+}
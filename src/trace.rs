@@ -0,0 +1,104 @@
+//! Hooks that observe [`try_map`](crate::ArrayMapExt::try_map), [`map2`](crate::ArrayMapExt::map2)
+//! and their variants while they process elements, for profiling or logging.
+//!
+//! These methods report every element to the hook:
+//!
+//! - [`ArrayMapExt`](crate::ArrayMapExt): `try_map`, `map2`, `opt_map`, `try_map_generic`, `map_break`,
+//!   `try_map_ref`, `try_map_cloned`, `try_map_copied`, `map_indexed`, `try_map_indexed`,
+//!   `try_map_enumerated`, `map_with`, `try_map_with` and `try_map_with_progress`.
+//! - [`ArrayConvertExt`](crate::ArrayConvertExt): `map_into`, `map_as`, `try_cast`, `try_map_into`,
+//!   `checked_cast` and `try_into_indices`.
+//!
+//! The other methods, and constructors like [`try_init_array`](crate::try_init_array), don't report.
+//!
+//! ```
+//! use array_try_map::trace::{self, Phase};
+//! use array_try_map::ArrayExt;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static STARTED: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn count_starts(_index: usize, phase: Phase) {
+//!     if phase == Phase::Start {
+//!         STARTED.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! trace::set_hook(Some(count_starts));
+//! let _ = [1u32, 2, 300].try_map_into::<u8>();
+//! let _ = [1, 2].try_map_ref(|x| Ok::<_, ()>(*x));
+//! trace::set_hook(None);
+//!
+//! assert_eq!(STARTED.load(Ordering::Relaxed), 5);
+//! ```
+//!
+//! A single global hook can be installed with [`set_hook`].
+//! When no hook is installed, the overhead is one atomic load per element and phase.
+//!
+//! ```
+//! use array_try_map::trace::{self, Phase};
+//! use array_try_map::ArrayExt;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static FAILURES: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn count_failures(_index: usize, phase: Phase) {
+//!     if phase == Phase::Err {
+//!         FAILURES.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! trace::set_hook(Some(count_failures));
+//! let _ = [1, 2, 3].try_map(|x| if x == 2 { Err(()) } else { Ok(x) });
+//! trace::set_hook(None);
+//!
+//! assert_eq!(FAILURES.load(Ordering::Relaxed), 1);
+//! ```
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The point in the processing of an element at which a hook is called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// The element is about to be passed to the function.
+    Start,
+    /// The function returned successfully.
+    Ok,
+    /// The function returned an error, which ends the map.
+    Err,
+}
+
+/// A function called with the index of an element and the phase it is in.
+pub type Hook = fn(usize, Phase);
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Installs `hook` to be called for every element processed on any thread,
+/// replacing the previous hook, or removes the hook if `hook` is `None`.
+pub fn set_hook(hook: Option<Hook>) {
+    let ptr = hook.map_or(core::ptr::null_mut(), |hook| hook as *mut ());
+    HOOK.store(ptr, Ordering::Release);
+}
+
+/// Returns the installed hook, if there is one.
+pub fn hook() -> Option<Hook> {
+    let ptr = HOOK.load(Ordering::Acquire);
+    if ptr.is_null() {
+        None
+    } else {
+        // SAFETY: non-null pointers are only ever stored by `set_hook`, from a `Hook`.
+        Some(unsafe { core::mem::transmute::<*mut (), Hook>(ptr) })
+    }
+}
+
+#[inline]
+pub(crate) fn emit(index: usize, phase: Phase) {
+    if let Some(hook) = hook() {
+        hook(index, phase);
+    }
+}
+
+#[inline]
+pub(crate) fn emit_result<U, E>(index: usize, res: &Result<U, E>) {
+    emit(index, if res.is_ok() { Phase::Ok } else { Phase::Err });
+}
@@ -0,0 +1,94 @@
+//! An array builder that tracks the number of initialized elements in its type.
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+use crate::assert::AssertLt;
+use crate::init::PartialInit;
+
+/// An array of length `N` whose first `FILLED` elements are initialized.
+///
+/// Every [`push`](TypedBuilder::push) returns a builder with one more initialized element,
+/// and [`finish`](TypedBuilder::finish) is only available once all `N` elements are initialized.
+/// Forgetting an element or pushing too many is therefore a compile-time error.
+///
+/// The initialized elements are dropped if the builder is dropped before being finished.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::TypedBuilder;
+/// let x = TypedBuilder::<u8, 3>::new().push(1).push(2).push(3).finish();
+/// assert_eq!(x, [1, 2, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::TypedBuilder;
+/// let x = TypedBuilder::<u8, 3>::new().push(1).push(2).finish();
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::TypedBuilder;
+/// let x = TypedBuilder::<u8, 1>::new().push(1).push(2);
+/// ```
+pub struct TypedBuilder<T, const N: usize, const FILLED: usize = 0> {
+    array: MaybeUninit<[T; N]>,
+}
+
+impl<T, const N: usize> TypedBuilder<T, N> {
+    /// Creates a builder with no initialized elements.
+    pub fn new() -> Self {
+        Self {
+            array: MaybeUninit::uninit(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for TypedBuilder<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, const FILLED: usize> TypedBuilder<T, N, FILLED> {
+    /// Returns the number of initialized elements.
+    pub const fn filled(&self) -> usize {
+        FILLED
+    }
+
+    /// Returns the initialized elements.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `FILLED` elements are initialized.
+        unsafe { core::slice::from_raw_parts(self.array.as_ptr().cast::<T>(), FILLED) }
+    }
+
+    /// Initializes the next element.
+    ///
+    /// Pushing onto a full builder is a compile-time error.
+    pub fn push(self, value: T) -> TypedBuilder<T, N, { FILLED + 1 }> {
+        let () = AssertLt::<FILLED, N>::OK;
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so ownership of the elements moves to `array`.
+        let mut array = unsafe { core::ptr::read(&this.array) };
+        // SAFETY: the first `FILLED` elements are initialized, and `FILLED` is less than `N`.
+        let mut writer = unsafe { PartialInit::resume(&mut array, FILLED) };
+        writer.write_next(value);
+        writer.finish_prefix_in_place();
+        TypedBuilder { array }
+    }
+}
+
+impl<T, const N: usize> TypedBuilder<T, N, N> {
+    /// Returns the initialized array.
+    pub fn finish(self) -> [T; N] {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, and all `N` elements are initialized.
+        unsafe { core::ptr::read(&this.array).assume_init() }
+    }
+}
+
+impl<T, const N: usize, const FILLED: usize> Drop for TypedBuilder<T, N, FILLED> {
+    fn drop(&mut self) {
+        // SAFETY: the first `FILLED` elements are initialized, and dropping the writer drops them.
+        drop(unsafe { PartialInit::resume(&mut self.array, FILLED) });
+    }
+}
@@ -0,0 +1,176 @@
+//! Fully unrolled `map` and `try_map` for chosen lengths.
+
+/// Defines a trait with fully unrolled versions of [`map2`](crate::ArrayMapExt::map2)
+/// and [`try_map`](crate::ArrayMapExt::try_map) for the given array lengths.
+///
+/// `unrolled_map_impls!(4, 8, 16)` defines a private trait `UnrolledMap<T, N>` in the calling module
+/// and implements it for `[T; 4]`, `[T; 8]` and `[T; 16]`.
+/// Its methods `map_unrolled` and `try_map_unrolled` destructure the array and call the closure
+/// once per element without a loop, which can help the optimizer on hot paths with small arrays.
+/// The generic methods are usually just as fast, so only use this after measuring.
+///
+/// Lengths from 1 to 32 are supported, and the macro can only be used once per module.
+/// The elements are processed in order, and like with the generic methods,
+/// the already built elements and the remaining input elements are dropped on errors and panics.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::unrolled_map_impls;
+/// use std::convert::TryFrom;
+///
+/// unrolled_map_impls!(4, 8);
+///
+/// let lanes = [1u32, 2, 3, 4].map_unrolled(|x| x * 10);
+/// assert_eq!(lanes, [10, 20, 30, 40]);
+///
+/// let bytes = [1u32, 2, 300, 4, 5, 6, 7, 8].try_map_unrolled(u8::try_from);
+/// assert!(bytes.is_err());
+/// ```
+///
+/// ```compile_fail
+/// # use array_try_map::unrolled_map_impls;
+/// unrolled_map_impls!(33);
+/// ```
+#[macro_export]
+macro_rules! unrolled_map_impls {
+    ($($n:tt),+ $(,)?) => {
+        /// Fully unrolled `map` and `try_map` for arrays of specific lengths.
+        trait UnrolledMap<T, const N: usize> {
+            /// Maps the elements like `map2`, without a loop.
+            fn map_unrolled<F, U>(self, f: F) -> [U; N]
+            where
+                F: ::core::ops::FnMut(T) -> U;
+
+            /// Maps the elements like `try_map`, without a loop.
+            fn try_map_unrolled<F, U, E>(self, f: F) -> ::core::result::Result<[U; N], E>
+            where
+                F: ::core::ops::FnMut(T) -> ::core::result::Result<U, E>;
+        }
+
+        $($crate::unrolled_map_impls!(@size $n);)+
+    };
+    (@impl $n:tt; $($x:ident)+) => {
+        impl<T> UnrolledMap<T, $n> for [T; $n] {
+            #[inline(always)]
+            fn map_unrolled<F, U>(self, mut f: F) -> [U; $n]
+            where
+                F: ::core::ops::FnMut(T) -> U,
+            {
+                let [$($x),+] = self;
+                [$(f($x)),+]
+            }
+
+            #[inline(always)]
+            fn try_map_unrolled<F, U, E>(self, mut f: F) -> ::core::result::Result<[U; $n], E>
+            where
+                F: ::core::ops::FnMut(T) -> ::core::result::Result<U, E>,
+            {
+                let [$($x),+] = self;
+                ::core::result::Result::Ok([$(f($x)?),+])
+            }
+        }
+    };
+    (@size 1) => {
+        $crate::unrolled_map_impls!(@impl 1; x0);
+    };
+    (@size 2) => {
+        $crate::unrolled_map_impls!(@impl 2; x0 x1);
+    };
+    (@size 3) => {
+        $crate::unrolled_map_impls!(@impl 3; x0 x1 x2);
+    };
+    (@size 4) => {
+        $crate::unrolled_map_impls!(@impl 4; x0 x1 x2 x3);
+    };
+    (@size 5) => {
+        $crate::unrolled_map_impls!(@impl 5; x0 x1 x2 x3 x4);
+    };
+    (@size 6) => {
+        $crate::unrolled_map_impls!(@impl 6; x0 x1 x2 x3 x4 x5);
+    };
+    (@size 7) => {
+        $crate::unrolled_map_impls!(@impl 7; x0 x1 x2 x3 x4 x5 x6);
+    };
+    (@size 8) => {
+        $crate::unrolled_map_impls!(@impl 8; x0 x1 x2 x3 x4 x5 x6 x7);
+    };
+    (@size 9) => {
+        $crate::unrolled_map_impls!(@impl 9; x0 x1 x2 x3 x4 x5 x6 x7 x8);
+    };
+    (@size 10) => {
+        $crate::unrolled_map_impls!(@impl 10; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9);
+    };
+    (@size 11) => {
+        $crate::unrolled_map_impls!(@impl 11; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10);
+    };
+    (@size 12) => {
+        $crate::unrolled_map_impls!(@impl 12; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11);
+    };
+    (@size 13) => {
+        $crate::unrolled_map_impls!(@impl 13; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12);
+    };
+    (@size 14) => {
+        $crate::unrolled_map_impls!(@impl 14; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13);
+    };
+    (@size 15) => {
+        $crate::unrolled_map_impls!(@impl 15; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14);
+    };
+    (@size 16) => {
+        $crate::unrolled_map_impls!(@impl 16; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15);
+    };
+    (@size 17) => {
+        $crate::unrolled_map_impls!(@impl 17; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16);
+    };
+    (@size 18) => {
+        $crate::unrolled_map_impls!(@impl 18; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17);
+    };
+    (@size 19) => {
+        $crate::unrolled_map_impls!(@impl 19; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18);
+    };
+    (@size 20) => {
+        $crate::unrolled_map_impls!(@impl 20; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19);
+    };
+    (@size 21) => {
+        $crate::unrolled_map_impls!(@impl 21; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20);
+    };
+    (@size 22) => {
+        $crate::unrolled_map_impls!(@impl 22; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21);
+    };
+    (@size 23) => {
+        $crate::unrolled_map_impls!(@impl 23; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22);
+    };
+    (@size 24) => {
+        $crate::unrolled_map_impls!(@impl 24; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23);
+    };
+    (@size 25) => {
+        $crate::unrolled_map_impls!(@impl 25; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24);
+    };
+    (@size 26) => {
+        $crate::unrolled_map_impls!(@impl 26; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25);
+    };
+    (@size 27) => {
+        $crate::unrolled_map_impls!(@impl 27; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26);
+    };
+    (@size 28) => {
+        $crate::unrolled_map_impls!(@impl 28; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27);
+    };
+    (@size 29) => {
+        $crate::unrolled_map_impls!(@impl 29; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27 x28);
+    };
+    (@size 30) => {
+        $crate::unrolled_map_impls!(@impl 30; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27 x28 x29);
+    };
+    (@size 31) => {
+        $crate::unrolled_map_impls!(@impl 31; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27 x28 x29 x30);
+    };
+    (@size 32) => {
+        $crate::unrolled_map_impls!(@impl 32; x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27 x28 x29 x30 x31);
+    };
+    (@size $n:tt) => {
+        ::core::compile_error!(::core::concat!(
+            "`unrolled_map_impls!` supports lengths from 1 to 32, not ",
+            ::core::stringify!($n)
+        ));
+    };
+}
@@ -0,0 +1,124 @@
+//! Arrays with one element per variant of a fieldless enum.
+
+use crate::ArrayExt;
+
+/// A fieldless enum with `N` variants.
+///
+/// This is usually implemented with [`impl_variant_array!`](crate::impl_variant_array),
+/// which checks at compile time that every variant is listed.
+///
+/// # Examples
+///
+/// ```
+/// # use array_try_map::{impl_variant_array, VariantArray};
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Dir {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// impl_variant_array!(Dir { North, East, South, West });
+///
+/// let names = Dir::from_variants(|dir| match dir {
+///     Dir::North => "N",
+///     Dir::East => "E",
+///     Dir::South => "S",
+///     Dir::West => "W",
+/// });
+/// assert_eq!(names, ["N", "E", "S", "W"]);
+/// assert_eq!(Dir::South.variant_index(), 2);
+/// ```
+pub trait VariantArray<const N: usize>: Sized {
+    /// Every variant, in the order of their indices.
+    const VARIANTS: [Self; N];
+
+    /// Returns the position of this variant in [`VARIANTS`](VariantArray::VARIANTS).
+    fn variant_index(&self) -> usize;
+
+    /// Creates an array by calling `f` with every variant in order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    fn from_variants<V, F>(f: F) -> [V; N]
+    where
+        F: FnMut(Self) -> V,
+    {
+        Self::VARIANTS.map2(f)
+    }
+
+    /// Creates an array by calling `f` with every variant in order until it returns an error.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns an [`Err`], that error will be returned by this function.
+    /// The already initialized elements will be dropped when an error occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `f` panics.
+    /// The already initialized elements will be dropped when a panic occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use array_try_map::{impl_variant_array, VariantArray};
+    /// #[derive(Debug)]
+    /// enum Channel {
+    ///     Red,
+    ///     Green,
+    ///     Blue,
+    /// }
+    ///
+    /// impl_variant_array!(Channel { Red, Green, Blue });
+    ///
+    /// let config = ["255", "128", "x"];
+    /// let levels = Channel::try_from_variants(|c| config[c.variant_index()].parse::<u8>());
+    /// assert!(levels.is_err());
+    /// ```
+    fn try_from_variants<V, E, F>(f: F) -> Result<[V; N], E>
+    where
+        F: FnMut(Self) -> Result<V, E>,
+    {
+        Self::VARIANTS.try_map(f)
+    }
+}
+
+/// Implements [`VariantArray`] for a fieldless enum.
+///
+/// Every variant must be listed exactly once, in the order they should have in arrays.
+/// Forgetting a variant is a compile-time error:
+///
+/// ```compile_fail
+/// # use array_try_map::impl_variant_array;
+/// enum Dir {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// impl_variant_array!(Dir { North, East, South });
+/// ```
+#[macro_export]
+macro_rules! impl_variant_array {
+    ($ty:ident { $($variant:ident),+ $(,)? }) => {
+        impl $crate::VariantArray<{ [$(stringify!($variant)),+].len() }> for $ty {
+            const VARIANTS: [Self; [$(stringify!($variant)),+].len()] = [$($ty::$variant),+];
+
+            fn variant_index(&self) -> usize {
+                #[allow(non_camel_case_types, dead_code)]
+                enum Index {
+                    $($variant),+
+                }
+
+                match self {
+                    $($ty::$variant => Index::$variant as usize),+
+                }
+            }
+        }
+    };
+}